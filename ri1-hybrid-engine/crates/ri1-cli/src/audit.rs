@@ -0,0 +1,203 @@
+//! Hash-chained audit log of run envelopes, and `ri1 audit verify`.
+//!
+//! Each line is an [`AuditRecord`]: the envelope, its sequence number, the hash of the record
+//! before it and its own SHA-256 over all three. Editing a record breaks its hash; deleting or
+//! reordering records breaks the sequence and the chain of `prev_hash` links. Dropping records
+//! from the end leaves a valid shorter chain, so reviewers keep the head hash `verify` prints.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+
+/// `prev_hash` of the first record.
+pub const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub seq: u64,
+    pub prev_hash: String,
+    pub hash: String,
+    /// Kept as written: the hash covers these exact bytes.
+    pub envelope: Box<RawValue>,
+}
+
+fn digest(seq: u64, prev_hash: &str, envelope: &RawValue) -> String {
+    let body = format!("{}\n{}\n{}", seq, prev_hash, envelope.get());
+    Sha256::digest(body.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Appends `envelope` to the log at `path`, chained to its last record. The file stays locked
+/// from reading the last record to writing the new one, so concurrent runs cannot fork the chain.
+pub fn append<T: Serialize>(path: &Path, envelope: &T) -> io::Result<AuditRecord> {
+    let mut file = OpenOptions::new().create(true).read(true).append(true).open(path)?;
+    file.lock()?;
+    let mut src = String::new();
+    file.read_to_string(&mut src)?;
+    let (seq, prev_hash) = match src.lines().rev().find(|l| !l.trim().is_empty()) {
+        Some(line) => {
+            let prev: AuditRecord = serde_json::from_str(line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            (prev.seq + 1, prev.hash)
+        }
+        None => (0, GENESIS.to_string()),
+    };
+    let envelope = serde_json::value::to_raw_value(envelope)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let record = AuditRecord { hash: digest(seq, &prev_hash, &envelope), seq, prev_hash, envelope };
+    writeln!(file, "{}", serde_json::to_string(&record).expect("audit records serialize"))?;
+    Ok(record)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Break {
+    Malformed(String),
+    /// A record is missing or out of place.
+    Sequence {
+        expected: u64,
+        found: u64,
+    },
+    /// `prev_hash` does not name the record before.
+    Link,
+    /// The record's content does not match its hash.
+    Modified,
+}
+
+/// First broken link of a chain, at a 1-based line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    pub line: usize,
+    pub kind: Break,
+}
+
+impl fmt::Display for BrokenLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            Break::Malformed(e) => write!(f, "line {}: malformed record: {}", self.line, e),
+            Break::Sequence { expected, found } => {
+                write!(
+                    f,
+                    "line {}: expected record {} but found {} (records deleted or reordered)",
+                    self.line, expected, found
+                )
+            }
+            Break::Link => {
+                write!(f, "line {}: prev_hash does not match the preceding record", self.line)
+            }
+            Break::Modified => {
+                write!(f, "line {}: record content does not match its hash", self.line)
+            }
+        }
+    }
+}
+
+/// Checks the chain in `src`; returns the record count and head hash.
+pub fn verify(src: &str) -> Result<(u64, String), BrokenLink> {
+    let mut expected = 0u64;
+    let mut prev = GENESIS.to_string();
+    for (i, line) in src.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let broken = |kind| BrokenLink { line: i + 1, kind };
+        let rec: AuditRecord =
+            serde_json::from_str(line).map_err(|e| broken(Break::Malformed(e.to_string())))?;
+        if rec.seq != expected {
+            return Err(broken(Break::Sequence { expected, found: rec.seq }));
+        }
+        if rec.prev_hash != prev {
+            return Err(broken(Break::Link));
+        }
+        if digest(rec.seq, &rec.prev_hash, &rec.envelope) != rec.hash {
+            return Err(broken(Break::Modified));
+        }
+        expected += 1;
+        prev = rec.hash;
+    }
+    Ok((expected, prev))
+}
+
+/// Returns 0 for an intact chain, 1 for a broken one and 2 when the log cannot be read.
+pub fn run_verify(path: &Path) -> i32 {
+    let src = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}: {}", path.display(), e);
+            return 2;
+        }
+    };
+    match verify(&src) {
+        Ok((count, head)) => {
+            println!("{}: ok, {} records, head {}", path.display(), count, head);
+            0
+        }
+        Err(b) => {
+            println!("{}: broken at {}", path.display(), b);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(name: &str, n: u64) -> Vec<String> {
+        let path =
+            std::env::temp_dir().join(format!("ri1-audit-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        for i in 0..n {
+            append(&path, &serde_json::json!({ "content": format!("run {}", i) })).unwrap();
+        }
+        let lines = fs::read_to_string(&path).unwrap().lines().map(str::to_owned).collect();
+        fs::remove_file(&path).unwrap();
+        lines
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let lines = log("intact", 3);
+        let (count, head) = verify(&lines.join("\n")).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(head, serde_json::from_str::<AuditRecord>(&lines[2]).unwrap().hash);
+    }
+
+    #[test]
+    fn tampering_reports_first_broken_link() {
+        let lines = log("tampered", 3);
+        let edited = lines[1].replace("run 1", "run X");
+        assert_eq!(
+            verify(&[lines[0].clone(), edited, lines[2].clone()].join("\n")).unwrap_err(),
+            BrokenLink { line: 2, kind: Break::Modified }
+        );
+        let deleted = [lines[0].clone(), lines[2].clone()].join("\n");
+        assert_eq!(verify(&deleted).unwrap_err().kind, Break::Sequence { expected: 1, found: 2 });
+        let swapped = [lines[1].clone(), lines[0].clone()].join("\n");
+        assert_eq!(
+            verify(&swapped).unwrap_err(),
+            BrokenLink { line: 1, kind: Break::Sequence { expected: 0, found: 1 } }
+        );
+        // Renumbering a record after deleting its predecessor still breaks the link.
+        let renumbered = [lines[0].clone(), lines[2].replace("\"seq\":2", "\"seq\":1")].join("\n");
+        assert_eq!(verify(&renumbered).unwrap_err().kind, Break::Link);
+    }
+
+    #[test]
+    fn concurrent_appends_keep_one_chain() {
+        let path =
+            std::env::temp_dir().join(format!("ri1-audit-concurrent-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    append(&path, &serde_json::json!({ "content": format!("run {}", i) })).unwrap()
+                })
+            })
+            .collect();
+        writers.into_iter().for_each(|w| drop(w.join().unwrap()));
+        assert_eq!(verify(&fs::read_to_string(&path).unwrap()).unwrap().0, 8);
+        fs::remove_file(&path).unwrap();
+    }
+}
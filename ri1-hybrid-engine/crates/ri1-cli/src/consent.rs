@@ -0,0 +1,124 @@
+//! `ri1 consent`: records grants and revocations in the consent ledger and lists them.
+
+use std::path::Path;
+
+use ri1_symbolic_meta::consent::ledger::now_unix_s;
+use ri1_symbolic_meta::{ConsentLedger, LedgerAction, LedgerError};
+
+/// Ledger used when `--ledger` is not given.
+pub const DEFAULT_LEDGER: &str = "ri1-consent.jsonl";
+
+/// Records a grant and prints its id. `expires_in` is in seconds from now (saturating at
+/// `u64::MAX`) and wins over `expires_at`. Returns 2 on ledger errors.
+pub fn grant(
+    ledger: &Path,
+    subject: &str,
+    scopes: Vec<String>,
+    expires_at: Option<u64>,
+    expires_in: Option<u64>,
+    reason: Option<String>,
+) -> i32 {
+    let expires_at = expires_in.map(|s| now_unix_s().saturating_add(s)).or(expires_at);
+    report(
+        ConsentLedger::open(ledger)
+            .and_then(|mut l| l.grant(subject, scopes, expires_at, reason).map(|e| e.id.clone())),
+    )
+}
+
+pub fn revoke(ledger: &Path, grant_id: &str, reason: Option<String>) -> i32 {
+    report(
+        ConsentLedger::open(ledger)
+            .and_then(|mut l| l.revoke(grant_id, reason).map(|e| e.id.clone())),
+    )
+}
+
+pub fn list(ledger: &Path) -> i32 {
+    match ConsentLedger::open(ledger) {
+        Ok(l) => {
+            print!("{}", render(&l, now_unix_s()));
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            2
+        }
+    }
+}
+
+fn report(recorded: Result<String, LedgerError>) -> i32 {
+    match recorded {
+        Ok(id) => {
+            println!("{}", id);
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            2
+        }
+    }
+}
+
+/// One line per entry; grants show their status at `now`.
+fn render(ledger: &ConsentLedger, now: u64) -> String {
+    let mut out = String::new();
+    for e in ledger.entries() {
+        let scopes = if e.scopes.is_empty() { "*".to_string() } else { e.scopes.join(",") };
+        let line = match e.action {
+            LedgerAction::Grant => {
+                let expires = e.expires_at.map(|t| format!(" expires={}", t)).unwrap_or_default();
+                format!(
+                    "{} grant {} scopes={}{} [{}]",
+                    e.id,
+                    e.subject,
+                    scopes,
+                    expires,
+                    ledger.status(e, now)
+                )
+            }
+            LedgerAction::Revoke => format!(
+                "{} revoke {} subject={}",
+                e.id,
+                e.revokes.as_deref().unwrap_or("?"),
+                e.subject
+            ),
+        };
+        out.push_str(&line);
+        if let Some(r) = &e.reason {
+            out.push_str(&format!(" reason={}", r));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_shows_status_of_each_grant() {
+        let path =
+            std::env::temp_dir().join(format!("ri1-cli-ledger-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut l = ConsentLedger::open(&path).unwrap();
+        l.grant("alice", vec!["text".into()], Some(100), None).unwrap();
+        l.grant("bob", Vec::new(), None, None).unwrap();
+        l.revoke("C0002", Some("left".into())).unwrap();
+        assert_eq!(
+            render(&l, 200),
+            "C0001 grant alice scopes=text expires=100 [expired]\nC0002 grant bob scopes=* [revoked by C0003]\nC0003 revoke C0002 subject=bob reason=left\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn expires_in_saturates() {
+        let path =
+            std::env::temp_dir().join(format!("ri1-cli-expiry-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(grant(&path, "alice", Vec::new(), None, Some(u64::MAX), None), 0);
+        let l = ConsentLedger::open(&path).unwrap();
+        assert_eq!(l.entries()[0].expires_at, Some(u64::MAX));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,161 @@
+//! Compiler-style rendering of resonance events against the content they were raised on.
+//!
+//! ```text
+//! violation[PHI006]: =: at end of expression; stabilization requires finalized form
+//!  --> text:1:15
+//!   |
+//! 1 | TEXT: Δ Λ Ψ Ω =
+//!   |               ^ `=`
+//!   |
+//!   = help: give '=' a finalized right-hand form, e.g. `Ψ = Ω`
+//! ```
+
+use std::fmt;
+
+use ri1_core::constraints::{OperatorClass, ResonanceEvent, SourceSpan};
+use ri1_symbolic_meta::interaction::rules::rule_info;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Violation,
+    Notice,
+    Info,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Violation => write!(f, "violation"),
+            Level::Notice => write!(f, "notice"),
+            Level::Info => write!(f, "info"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub code: Option<String>,
+    pub message: String,
+    pub span: Option<SourceSpan>,
+    pub label: Option<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn from_event(e: &ResonanceEvent) -> Self {
+        let level = match e.operator {
+            OperatorClass::InteractionViolation => Level::Violation,
+            OperatorClass::InteractionNotice => Level::Notice,
+            _ => Level::Info,
+        };
+        // Rule events are identified by their rule code; gate events by their operator class.
+        let code = match (&e.code, level) {
+            (Some(c), _) => Some(c.clone()),
+            (None, Level::Info) => Some(format!("{:?}", e.operator)),
+            (None, _) => None,
+        };
+        let label = e.symbol.as_ref().map(|s| match &e.section_ref {
+            Some(sec) => format!("`{}` §{}", s, sec),
+            None => format!("`{}`", s),
+        });
+        let help = e.code.as_deref().and_then(rule_info).map(|r| r.help.to_string());
+        Self { level, code, message: e.message.clone(), span: e.span, label, help }
+    }
+}
+
+/// Renders diagnostics for one source text; `origin` names it in the `-->` line.
+pub struct Renderer<'a> {
+    origin: &'a str,
+    source: &'a str,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(origin: &'a str, source: &'a str) -> Self {
+        Self { origin, source }
+    }
+
+    pub fn render(&self, d: &Diagnostic) -> String {
+        let mut out = match &d.code {
+            Some(code) => format!("{}[{}]: {}\n", d.level, code, d.message),
+            None => format!("{}: {}\n", d.level, d.message),
+        };
+        let span = d.span.filter(|s| s.start <= s.end && s.end <= self.source.len());
+        let Some(span) = span else {
+            if let Some(help) = &d.help {
+                out.push_str(&format!("  = help: {}\n", help));
+            }
+            return out;
+        };
+
+        let line_text = self.source.lines().nth(span.line - 1).unwrap_or("");
+        let gutter = " ".repeat(span.line.to_string().len());
+        // Carets cover the span's chars on its first line, at least one.
+        let rest_of_line = line_text.chars().count().saturating_sub(span.column - 1);
+        let width = self.source[span.start..span.end]
+            .chars()
+            .take_while(|c| *c != '\n')
+            .count()
+            .min(rest_of_line)
+            .max(1);
+
+        out.push_str(&format!("{}--> {}:{}:{}\n", gutter, self.origin, span.line, span.column));
+        out.push_str(&format!("{} |\n", gutter));
+        out.push_str(&format!("{} | {}\n", span.line, line_text));
+        out.push_str(&format!("{} | {}{}", gutter, " ".repeat(span.column - 1), "^".repeat(width)));
+        if let Some(label) = &d.label {
+            out.push_str(&format!(" {}", label));
+        }
+        out.push('\n');
+        if let Some(help) = &d.help {
+            out.push_str(&format!("{} |\n{} = help: {}\n", gutter, gutter, help));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        operator: OperatorClass,
+        code: Option<&str>,
+        span: Option<SourceSpan>,
+    ) -> ResonanceEvent {
+        ResonanceEvent {
+            operator,
+            message: "msg".into(),
+            section_ref: None,
+            symbol: Some("=".into()),
+            code: code.map(String::from),
+            span,
+            suggestions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_carets_under_span() {
+        let src = "Ψ : Φ\n[ΔΨ] → Ξ =";
+        let at = src.rfind('=').unwrap();
+        let span = SourceSpan::locate(src, at, at + 1);
+        let e = event(OperatorClass::InteractionViolation, Some("PHI006"), Some(span));
+        let out = Renderer::new("text", src).render(&Diagnostic::from_event(&e));
+        let expected = "violation[PHI006]: msg\n \
+                        --> text:2:10\n  \
+                        |\n\
+                        2 | [ΔΨ] → Ξ =\n  \
+                        |          ^ `=`\n  \
+                        |\n  \
+                        = help: give '=' a finalized right-hand form, e.g. `Ψ = Ω`\n";
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn renders_events_without_location() {
+        let mut e = event(OperatorClass::InteractionNotice, None, None);
+        e.symbol = None;
+        let out = Renderer::new("text", "Φ").render(&Diagnostic::from_event(&e));
+        assert_eq!(out, "notice: msg\n");
+    }
+}
@@ -0,0 +1,84 @@
+//! `ri1 fix`: applies the safe suggestions attached to interaction violations.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use ri1_symbolic_meta::{fix_source, ValidatorConfig};
+
+/// Fixes each of `paths` in place, or stdin to stdout when no paths are given. With `diff`
+/// nothing is written and the changes are printed instead; returns 1 if there were any.
+/// Rules disabled by the `rules` config offer no fixes. Returns 2 on read or write errors.
+pub fn run(paths: Vec<PathBuf>, diff: bool, rules: Option<PathBuf>) -> i32 {
+    let cfg = match rules.map(ValidatorConfig::load).transpose() {
+        Ok(cfg) => cfg.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 2;
+        }
+    };
+    if paths.is_empty() {
+        let mut src = String::new();
+        if let Err(e) = io::stdin().read_to_string(&mut src) {
+            eprintln!("error: reading stdin: {}", e);
+            return 2;
+        }
+        let out = fix_source(&src, &cfg).output;
+        if diff {
+            print!("{}", render_diff(Path::new("<stdin>"), &src, &out));
+            return i32::from(out != src);
+        }
+        print!("{}", out);
+        return 0;
+    }
+
+    let mut code = 0;
+    for path in paths {
+        let src = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("error: {}: {}", path.display(), e);
+                code = 2;
+                continue;
+            }
+        };
+        let fixed = fix_source(&src, &cfg);
+        if fixed.output == src {
+            continue;
+        }
+        if diff {
+            print!("{}", render_diff(&path, &src, &fixed.output));
+            code = code.max(1);
+        } else if let Err(e) = fs::write(&path, &fixed.output) {
+            eprintln!("error: {}: {}", path.display(), e);
+            code = 2;
+        } else {
+            for s in &fixed.applied {
+                println!("{}: {}", path.display(), s.message);
+            }
+        }
+    }
+    code
+}
+
+/// Line diff; fixes never add or remove lines, so changed lines pair up one to one.
+fn render_diff(path: &Path, before: &str, after: &str) -> String {
+    let mut out = format!("--- {}\n+++ {}\n", path.display(), path.display());
+    for (i, (a, b)) in before.lines().zip(after.lines()).enumerate() {
+        if a != b {
+            out.push_str(&format!("@@ line {} @@\n-{}\n+{}\n", i + 1, a, b));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lists_changed_lines() {
+        let out = render_diff(Path::new("f.phi"), "Ψ : Φ\nΨ =\n", "Ψ : Φ\nΨ\n");
+        assert_eq!(out, "--- f.phi\n+++ f.phi\n@@ line 2 @@\n-Ψ =\n+Ψ\n");
+    }
+}
@@ -0,0 +1,128 @@
+//! `ri1 fmt`: rewrites Phipe expression files into canonical form, optionally switching
+//! between glyph and ASCII spelling.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use ri1_symbolic_meta::{
+    format_expr, format_source, normalize_aliases, to_ascii, to_glyphs, Catalog, FormatError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spelling {
+    AsWritten,
+    Glyphs,
+    Ascii,
+}
+
+/// Formats `src`, reading ASCII aliases as glyphs first.
+fn render(src: &str, spelling: Spelling) -> Result<String, FormatError> {
+    let catalog = Catalog::builtin();
+    if spelling == Spelling::AsWritten {
+        return render_as_written(src, &catalog);
+    }
+    let out = format_source(&to_glyphs(src, &catalog))?;
+    Ok(if spelling == Spelling::Ascii { to_ascii(&out, &catalog) } else { out })
+}
+
+/// Formats line by line, spelling a line back in ASCII when it was written with aliases.
+/// Each line keeps its terminator, as in `format_source`.
+fn render_as_written(src: &str, catalog: &Catalog) -> Result<String, FormatError> {
+    let mut out = String::with_capacity(src.len());
+    for (i, line) in src.split_inclusive('\n').enumerate() {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let glyphs = normalize_aliases(body, catalog);
+        let formatted = if glyphs.aliased() {
+            format_expr(&glyphs.text).map(|f| to_ascii(&f, catalog))
+        } else {
+            format_expr(body)
+        };
+        out.push_str(&formatted.map_err(|e| FormatError { line: i + 1, ..e })?);
+        out.push_str(&line[body.len()..]);
+    }
+    Ok(out)
+}
+
+/// Formats each of `paths` in place, or stdin to stdout when no paths are given.
+/// With `check` nothing is written; returns 1 if any input is not already canonical and
+/// 2 on read or parse errors.
+pub fn run(paths: Vec<PathBuf>, check: bool, spelling: Spelling) -> i32 {
+    if paths.is_empty() {
+        let mut src = String::new();
+        if let Err(e) = io::stdin().read_to_string(&mut src) {
+            eprintln!("error: reading stdin: {}", e);
+            return 2;
+        }
+        return match render(&src, spelling) {
+            Ok(out) if check => i32::from(out != src),
+            Ok(out) => {
+                print!("{}", out);
+                0
+            }
+            Err(e) => {
+                eprintln!("error: <stdin> {}", e);
+                2
+            }
+        };
+    }
+
+    let mut code = 0;
+    for path in paths {
+        let src = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("error: {}: {}", path.display(), e);
+                code = 2;
+                continue;
+            }
+        };
+        match render(&src, spelling) {
+            Ok(out) if out == src => {}
+            Ok(_) if check => {
+                println!("would reformat {}", path.display());
+                code = code.max(1);
+            }
+            Ok(out) => {
+                if let Err(e) = fs::write(&path, out) {
+                    eprintln!("error: {}: {}", path.display(), e);
+                    code = 2;
+                }
+            }
+            Err(e) => {
+                eprintln!("error: {} {}", path.display(), e);
+                code = 2;
+            }
+        }
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spellings_round_trip() {
+        let ascii = render("Gamma.dot^n:\\Sigma(Psi)->Omega\n", Spelling::Ascii).unwrap();
+        assert_eq!(ascii, "Gamma.dot^n : Sigma(Psi) -> Omega\n");
+        let glyphs = render(&ascii, Spelling::Glyphs).unwrap();
+        assert_eq!(glyphs, "Γ̇ⁿ : Σ(Ψ) → Ω\n");
+        assert_eq!(render(&glyphs, Spelling::Ascii).unwrap(), ascii);
+        assert_eq!(render("Phi -> Omega\n", Spelling::AsWritten).unwrap(), "Phi -> Omega\n");
+    }
+
+    #[test]
+    fn as_written_formats_aliases_in_their_spelling() {
+        let out = render("Psi->Omega\nΕΔΦ→Ω\n\\Sigma(Psi,Gamma)", Spelling::AsWritten).unwrap();
+        assert_eq!(out, "Psi -> Omega\nΕ Δ Φ → Ω\nSigma(Psi, Gamma)");
+        assert!(render("Phi[->Omega\n", Spelling::AsWritten).is_err());
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let src = "Psi -> Omega\r\nΕ Δ Φ → Ω\r\n";
+        assert_eq!(render(src, Spelling::AsWritten).unwrap(), src);
+        assert_eq!(render("Ψ→Ω\r\n", Spelling::Glyphs).unwrap(), "Ψ → Ω\r\n");
+    }
+}
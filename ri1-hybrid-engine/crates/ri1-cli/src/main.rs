@@ -1,16 +1,22 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use ri1_core::modality::GenerationRequest;
-use ri1_core::Orchestrator;
-use ri1_core::constraints::{ResonanceEvent, OperatorClass, ConstraintResult};
-use ri1_symbolic_meta::{MetaEngineImpl, InfluenceSnapshot, compute_influence};
+use ri1_core::{InoculationGuard, InoculationLimits, InoculationRecord, Orchestrator, OrchestratorError, Timings};
+use ri1_core::constraints::{ResonanceEvent, ConstraintResult, FieldContext};
+use ri1_symbolic_meta::{ConsentLedger, DeclarativePolicy, FieldPhases, MetaEngineImpl, InfluenceSnapshot, ValidatorConfig};
 use ri1_text::BasicText;
-use tracing::{info, warn};
+use tracing::info;
 use tracing_subscriber::EnvFilter;
-use serde_json;
 use uuid::Uuid;
 use std::path::PathBuf;
 use std::fs;
 
+mod audit;
+mod consent;
+mod diagnostics;
+mod fix;
+mod fmt;
+use diagnostics::{Diagnostic, Renderer};
+
 #[derive(Parser, Debug)]
 #[command(name = "ri1", version, about = "RI1 Hybrid Generative Engine CLI")]
 struct Cli {
@@ -23,33 +29,190 @@ enum Commands {
     /// Generate content
     Gen {
         #[command(subcommand)]
-        modality: GenModality,
+        modality: Box<GenModality>,
     },
-}
-
-#[derive(Subcommand, Debug)]
-enum GenModality {
-    /// Text generation
-    Text {
-        /// Prompt string
-        #[arg(short, long)]
-        prompt: String,
-        /// Print resonance events
+    /// Format Phipe expressions canonically (one expression per line)
+    Fmt {
+        /// Files to format in place; reads stdin and writes stdout when omitted
+        paths: Vec<PathBuf>,
+        /// Exit non-zero instead of writing when any input is not formatted
         #[arg(long, default_value_t = false)]
-        verbose: bool,
-        /// Output resonance events as JSON
+        check: bool,
+        /// Spell ASCII aliases such as `Phi` or `->` as glyphs
+        #[arg(long, default_value_t = false, conflicts_with = "ascii")]
+        glyphs: bool,
+        /// Spell glyphs with their ASCII aliases
         #[arg(long, default_value_t = false)]
-        json: bool,
-        /// Show influence block in verbose output
+        ascii: bool,
+    },
+    /// Apply safe fixes suggested by interaction rules (one expression per line)
+    Fix {
+        /// Files to fix in place; reads stdin and writes stdout when omitted
+        paths: Vec<PathBuf>,
+        /// Print the changes as a diff instead of writing them
         #[arg(long, default_value_t = false)]
-        influence: bool,
-        /// Optional correlation id (uuid); if not provided, generated per run
+        diff: bool,
+        /// TOML file enabling/disabling interaction rules; disabled rules offer no fixes
+        #[arg(long)]
+        rules: Option<PathBuf>,
+    },
+    /// Check hash-chained audit logs written by `gen --audit-log`
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+    /// Record and inspect consent grants in the append-only ledger
+    Consent {
+        /// Ledger file (JSON lines)
+        #[arg(long, global = true, default_value = consent::DEFAULT_LEDGER)]
+        ledger: PathBuf,
+        #[command(subcommand)]
+        action: ConsentAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AuditAction {
+    /// Verify the hash chain; reports the first broken link
+    Verify { log: PathBuf },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConsentAction {
+    /// Grant consent for a subject; prints the grant id
+    Grant {
+        subject: String,
+        /// Modalities covered (`text`, `*`, `prefix*`); all when omitted
+        #[arg(long = "scope")]
+        scopes: Vec<String>,
+        /// Unix time (seconds) at which the grant expires
+        #[arg(long, conflicts_with = "expires_in")]
+        expires_at: Option<u64>,
+        /// Seconds from now until the grant expires
         #[arg(long)]
-        cid: Option<String>,
-        /// Write a JSON envelope (with cid, content, constraints, events) to file
+        expires_in: Option<u64>,
         #[arg(long)]
-        log_file: Option<PathBuf>,
+        reason: Option<String>,
     },
+    /// Revoke a grant by id
+    Revoke {
+        id: String,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// List grants and revocations with each grant's current status
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+enum GenModality {
+    /// Text generation
+    Text(TextArgs),
+}
+
+/// Options of `ri1 gen text`.
+#[derive(Args, Debug)]
+struct TextArgs {
+    /// Prompt string
+    #[arg(short, long)]
+    prompt: String,
+    /// Print resonance events
+    #[arg(long, default_value_t = false)]
+    verbose: bool,
+    /// Output resonance events as JSON
+    #[arg(long, default_value_t = false)]
+    json: bool,
+    /// Show influence block in verbose output
+    #[arg(long, default_value_t = false)]
+    influence: bool,
+    /// Optional correlation id (uuid); if not provided, generated per run
+    #[arg(long)]
+    cid: Option<String>,
+    /// Write a JSON envelope (with cid, content, constraints, events) to file
+    #[arg(long)]
+    log_file: Option<PathBuf>,
+    /// Append the JSON envelope to a hash-chained audit log
+    #[arg(long)]
+    audit_log: Option<PathBuf>,
+    /// TOML file enabling/disabling interaction rules or overriding their severity
+    #[arg(long)]
+    rules: Option<PathBuf>,
+    /// TOML consent policy deciding whether generation may proceed; grants all when omitted
+    #[arg(long)]
+    consent_policy: Option<PathBuf>,
+    /// Consent ledger that must hold a live grant for the request
+    #[arg(long)]
+    consent_ledger: Option<PathBuf>,
+    /// Read ASCII operator spellings (`Phi`, `->`) in the output as glyphs
+    #[arg(long, default_value_t = false)]
+    ascii_input: bool,
+    #[command(flatten)]
+    inoculation: InoculationArgs,
+    #[command(flatten)]
+    field: FieldArgs,
+}
+
+/// Limits on recursive generation; the guard is in-memory unless a journal is given.
+#[derive(Args, Debug)]
+struct InoculationArgs {
+    /// Journal of inoculation decisions and outputs, so limits hold across runs
+    #[arg(long)]
+    inoculation_journal: Option<PathBuf>,
+    /// Feedback generations allowed on top of a fresh prompt
+    #[arg(long, default_value_t = InoculationLimits::default().max_depth)]
+    max_depth: usize,
+    /// Times the same prompt may be admitted within `--repetition-window`
+    #[arg(long, default_value_t = InoculationLimits::default().max_repetitions)]
+    max_repetitions: usize,
+    /// Seconds an admission counts towards `--max-repetitions`
+    #[arg(long, default_value_t = InoculationLimits::default().repetition_window_s)]
+    repetition_window: u64,
+}
+
+impl InoculationArgs {
+    fn guard(&self) -> std::io::Result<InoculationGuard> {
+        let limits = InoculationLimits {
+            max_depth: self.max_depth,
+            max_repetitions: self.max_repetitions,
+            repetition_window_s: self.repetition_window,
+            ..InoculationLimits::default()
+        };
+        match &self.inoculation_journal {
+            Some(path) => InoculationGuard::persistent(limits, path),
+            None => Ok(InoculationGuard::new(limits)),
+        }
+    }
+}
+
+/// Field context the request is generated and evaluated in.
+#[derive(Args, Debug)]
+struct FieldArgs {
+    /// Field phase, one of those declared by `--phases` (built in: alpha, beta, gamma, delta, omega)
+    #[arg(long, default_value = "alpha")]
+    phase: String,
+    /// TOML file declaring field phases, their transitions and permitted operators
+    #[arg(long)]
+    phases: Option<PathBuf>,
+    #[arg(long)]
+    field_id: Option<String>,
+    /// Where the request comes from
+    #[arg(long)]
+    source: Option<String>,
+    /// Whose consent governs the request
+    #[arg(long)]
+    subject: Option<String>,
+}
+
+impl FieldArgs {
+    fn context(&self) -> FieldContext {
+        FieldContext {
+            phase: Some(self.phase.clone()),
+            field_id: self.field_id.clone(),
+            source: self.source.clone(),
+            subject: self.subject.clone(),
+            modality: None,
+        }
+    }
 }
 
 fn main() {
@@ -59,9 +222,20 @@ fn main() {
 
     let cli = Cli::parse();
     match cli.command {
-        Commands::Gen { modality } => match modality {
-            GenModality::Text { prompt, verbose, json, influence, cid, log_file } => gen_text(prompt, verbose, json, influence, cid, log_file),
+        Commands::Gen { modality } => match *modality {
+            GenModality::Text(args) => gen_text(args),
         },
+        Commands::Fmt { paths, check, glyphs, ascii } => {
+            let spelling = if ascii { fmt::Spelling::Ascii } else if glyphs { fmt::Spelling::Glyphs } else { fmt::Spelling::AsWritten };
+            std::process::exit(fmt::run(paths, check, spelling))
+        }
+        Commands::Fix { paths, diff, rules } => std::process::exit(fix::run(paths, diff, rules)),
+        Commands::Audit { action: AuditAction::Verify { log } } => std::process::exit(audit::run_verify(&log)),
+        Commands::Consent { ledger, action } => std::process::exit(match action {
+            ConsentAction::Grant { subject, scopes, expires_at, expires_in, reason } => consent::grant(&ledger, &subject, scopes, expires_at, expires_in, reason),
+            ConsentAction::Revoke { id, reason } => consent::revoke(&ledger, &id, reason),
+            ConsentAction::List => consent::list(&ledger),
+        }),
     }
 }
 
@@ -73,79 +247,189 @@ struct LogEnvelope {
     constraints: Vec<ConstraintResult>,
     events: Vec<ResonanceEvent>,
     timestamp_unix_s: u64,
-    influence: InfluenceSnapshot,
+    influence: Option<InfluenceSnapshot>,
+    timings: Timings,
+    inoculation: Vec<InoculationRecord>,
+    /// Why nothing was generated; the other fields are then empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<OrchestratorError>,
+}
+
+/// Writes `env` to `--log-file` and appends it to `--audit-log`, exiting with 2 when the audit
+/// log cannot be written.
+fn write_envelope(env: &LogEnvelope, log_file: Option<PathBuf>, audit_log: Option<PathBuf>) {
+    if let Some(path) = log_file {
+        if let Ok(s) = serde_json::to_string_pretty(env) {
+            let _ = fs::write(path, s);
+        }
+    }
+    if let Some(path) = audit_log {
+        if let Err(e) = audit::append(&path, env) {
+            eprintln!("error: {}: {}", path.display(), e);
+            std::process::exit(2);
+        }
+    }
 }
 
-fn gen_text(prompt: String, verbose: bool, json: bool, influence_flag: bool, cid: Option<String>, log_file: Option<PathBuf>) {
+fn now_unix_s() -> u64 { std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0) }
+
+fn gen_text(args: TextArgs) {
+    let TextArgs { prompt, verbose, json, influence: influence_flag, cid, log_file, audit_log, rules, consent_policy, consent_ledger, ascii_input, inoculation: inoculation_args, field } = args;
+    let validator = match rules.map(ValidatorConfig::load).transpose() {
+        Ok(cfg) => cfg.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    let mut engine = MetaEngineImpl::new_default().with_validator_config(validator).with_alias_input(ascii_input);
+    match consent_policy.map(DeclarativePolicy::load).transpose() {
+        Ok(Some(policy)) => engine = engine.with_consent_policy(policy),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    }
+    match field.phases.as_ref().map(FieldPhases::load).transpose() {
+        Ok(Some(phases)) => engine = engine.with_field_phases(phases),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    }
+    let ctx = field.context();
+    match consent_ledger.map(ConsentLedger::open).transpose() {
+        Ok(Some(ledger)) => engine = engine.with_consent_ledger(ledger),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    }
     let mut orch = Orchestrator::new();
     orch.register_modality(BasicText);
-    orch.set_meta_engine(MetaEngineImpl::new_default());
+    orch.set_meta_engine(engine);
+    match inoculation_args.guard() {
+        Ok(guard) => orch.set_inoculation_guard(guard),
+        Err(e) => {
+            let journal = inoculation_args.inoculation_journal.unwrap_or_default();
+            eprintln!("error: {}: {}", journal.display(), e);
+            std::process::exit(2);
+        }
+    }
 
     info!("modalities = {:?}", orch.modalities());
-    let req = GenerationRequest { prompt };
-    if let Some(res) = orch.generate("text", req) {
-        println!("{}", res.content);
-        let (log, mut events) = orch.evaluate_with_events("text", &res.content);
-        // Correlation ID event injection
-        let cid_val = cid.unwrap_or_else(|| Uuid::new_v4().to_string());
-        let corr_event = ResonanceEvent {
-            operator: OperatorClass::InteractionNotice,
-            message: format!("correlation_id: {}", cid_val),
-            section_ref: None,
-            symbol: None,
-        };
-        events.insert(0, corr_event);
-
-        // Influence snapshot (logging-only)
-        let (influence, _infl_notice) = compute_influence(&events);
-
-        // Optional file logging of JSON envelope
-        if let Some(path) = log_file {
-            let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
-            let env = LogEnvelope {
-                correlation_id: cid_val.clone(),
-                modality: "text".into(),
-                content: res.content.clone(),
-                constraints: log.clone(),
-                events: events.clone(),
-                timestamp_unix_s: ts,
-                influence: influence.clone(),
-            };
-            if let Ok(s) = serde_json::to_string_pretty(&env) {
-                let _ = fs::write(path, s);
+    // Blocked runs are logged too, so the id is fixed before generating.
+    let cid = cid.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let req = GenerationRequest { prompt, correlation_id: Some(cid.clone()) };
+    let generated = orch.generate_in_context("text", req, &ctx);
+    // This run's admission decision; the guard also holds those of earlier runs.
+    let inoculation: Vec<_> = orch.inoculation_records().pop().into_iter().collect();
+    let report = match generated {
+        Ok(report) => report,
+        Err(e) => {
+            report_generation_error(&e);
+            if log_file.is_some() || audit_log.is_some() {
+                let env = LogEnvelope {
+                    correlation_id: cid,
+                    modality: "text".into(),
+                    content: String::new(),
+                    constraints: Vec::new(),
+                    events: Vec::new(),
+                    timestamp_unix_s: now_unix_s(),
+                    influence: None,
+                    timings: Timings::default(),
+                    inoculation: inoculation.clone(),
+                    error: Some(e.clone()),
+                };
+                write_envelope(&env, log_file, audit_log);
             }
+            std::process::exit(exit_code(&e));
+        }
+    };
+    println!("{}", report.content);
+
+    // Optional file logging of JSON envelope
+    if log_file.is_some() || audit_log.is_some() {
+        let env = LogEnvelope {
+            correlation_id: report.correlation_id.clone(),
+            modality: report.modality.clone(),
+            content: report.content.clone(),
+            constraints: report.results.clone(),
+            events: report.events.clone(),
+            timestamp_unix_s: now_unix_s(),
+            influence: report.influence.clone(),
+            timings: report.timings,
+            inoculation,
+            error: None,
+        };
+        write_envelope(&env, log_file, audit_log);
+    }
+    if json || verbose {
+        println!("correlation_id = {}", report.correlation_id);
+    }
+    if json {
+        if !report.events.is_empty() {
+            let s = serde_json::to_string_pretty(&report.events).unwrap_or_else(|_| "[]".into());
+            println!("--- resonance(json) ---\n{}", s);
         }
-        if json {
-            if !events.is_empty() {
-                let s = serde_json::to_string_pretty(&events).unwrap_or_else(|_| "[]".into());
-                println!("--- resonance(json) ---\n{}", s);
+    } else if verbose {
+        if !report.events.is_empty() {
+            println!("--- resonance ---");
+            let renderer = Renderer::new("text", &report.content);
+            for e in &report.events {
+                print!("{}", renderer.render(&Diagnostic::from_event(e)));
             }
-        } else if verbose {
-            if !events.is_empty() {
-                println!("--- resonance ---");
-                for e in events {
-                    let sym = e.symbol.as_deref().unwrap_or("?");
-                    let sec = e.section_ref.as_deref().unwrap_or("-");
-                    println!("{} [{}] {}: {}", sym, sec, format!("{:?}", e.operator), e.message);
-                }
+        }
+        if let Some(influence) = report.influence.as_ref().filter(|_| influence_flag) {
+            println!("--- influence ---");
+            println!("resonance_index = {:.2}", influence.resonance_index);
+            if !influence.operator_influence.is_empty() {
+                println!("top = {}", influence.operator_influence.iter().take(3).map(|ow| format!("{:?}:{:.2}", ow.operator, ow.weight)).collect::<Vec<_>>().join(", "));
             }
-            if influence_flag {
-                println!("--- influence ---");
-                println!("resonance_index = {:.2}", influence.resonance_index);
-                if !influence.operator_influence.is_empty() {
-                    println!("top = {}", influence.operator_influence.iter().take(3).map(|ow| format!("{:?}:{:.2}", ow.operator, ow.weight)).collect::<Vec<_>>().join(", "));
+            println!("cooperation = {}  conflict = {}", influence.cooperation_count, influence.conflict_count);
+        }
+    }
+    if !report.results.is_empty() {
+        println!("--- constraints ---");
+        for r in report.results {
+            println!("{} [{}]: {}", r.name, r.severity, r.message.unwrap_or_else(|| "ok".into()));
+        }
+    }
+}
+
+/// Exit codes of `ri1 gen` when nothing is generated.
+const EXIT_USAGE: i32 = 2;
+const EXIT_CONSENT_DENIED: i32 = 3;
+const EXIT_HARD_CONSTRAINTS: i32 = 4;
+const EXIT_INOCULATION: i32 = 5;
+
+fn exit_code(e: &OrchestratorError) -> i32 {
+    match e {
+        OrchestratorError::UnknownModality(_) => EXIT_USAGE,
+        OrchestratorError::ConsentDenied(_) => EXIT_CONSENT_DENIED,
+        OrchestratorError::HardConstraints { .. } => EXIT_HARD_CONSTRAINTS,
+        OrchestratorError::Inoculation(_) => EXIT_INOCULATION,
+    }
+}
+
+fn report_generation_error(e: &OrchestratorError) {
+    eprintln!("error: {}", e);
+    match e {
+        OrchestratorError::ConsentDenied(c) => {
+            for (label, value) in [("ref", &c.section_ref), ("ledger", &c.ledger_id)] {
+                if let Some(v) = value {
+                    eprintln!("  {} = {}", label, v);
                 }
-                println!("cooperation = {}  conflict = {}", influence.cooperation_count, influence.conflict_count);
             }
         }
-        if !log.is_empty() {
-            println!("--- constraints ---");
-            for r in log {
-                println!("{} [{}]: {}", r.name, r.severity, r.message.unwrap_or_else(|| "ok".into()));
+        OrchestratorError::HardConstraints { failures, .. } => {
+            for r in failures {
+                eprintln!("  {} [{}]: {}", r.name, r.severity, r.message.as_deref().unwrap_or("failed"));
             }
         }
-    } else {
-        warn!("generation failed or blocked by constraints");
-        eprintln!("error: generation failed or blocked by constraints");
+        OrchestratorError::UnknownModality(_) | OrchestratorError::Inoculation(_) => {}
     }
 }
@@ -1,9 +1,11 @@
 use std::fmt;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Severity {
+    #[serde(alias = "soft")]
     Soft,
+    #[serde(alias = "hard")]
     Hard,
 }
 
@@ -16,12 +18,51 @@ impl fmt::Display for Severity {
     }
 }
 
+/// Location of a finding in the evaluated content: byte range `start..end` plus the
+/// 1-based line and column (in chars) of `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceSpan {
+    /// Resolves line/column for the byte range `start..end` of `source`.
+    pub fn locate(source: &str, start: usize, end: usize) -> Self {
+        let before = &source[..start.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        Self { start, end, line, column }
+    }
+}
+
+/// Replace `span` of the evaluated content with `replacement` (empty to delete; an empty span
+/// inserts).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Edit {
+    pub span: SourceSpan,
+    pub replacement: String,
+}
+
+/// A fix for one finding, made of non-overlapping edits applied together.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Suggestion {
+    pub message: String,
+    pub edits: Vec<Edit>,
+    /// Preserves the author's intent and can be applied without review.
+    pub safe: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ConstraintResult {
     pub passed: bool,
     pub severity: Severity,
     pub name: &'static str,
     pub message: Option<String>,
+    pub span: Option<SourceSpan>,
 }
 
 pub trait Constraint {
@@ -34,7 +75,7 @@ pub trait ConstraintEngine: Send + Sync {
 }
 
 // --- Phase 2: Meta Engine Interfaces ---
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OperatorClass {
     Coexistence,
     Fusion,
@@ -74,6 +115,8 @@ pub struct Consent {
     pub subject: Option<String>,
     pub reason: Option<String>,
     pub section_ref: Option<String>,
+    /// Consent ledger entry behind the decision, when one was consulted.
+    pub ledger_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -81,10 +124,35 @@ pub struct FieldContext {
     pub phase: Option<String>,
     pub source: Option<String>,
     pub field_id: Option<String>,
+    /// Whose consent governs the request.
+    pub subject: Option<String>,
+    pub modality: Option<String>,
 }
 
 impl Default for FieldContext {
-    fn default() -> Self { Self { phase: Some("alpha".into()), source: None, field_id: None } }
+    fn default() -> Self { Self { phase: Some("alpha".into()), source: None, field_id: None, subject: None, modality: None } }
+}
+
+/// Decides whether a request may proceed in the given field context.
+pub trait ConsentPolicy: Send + Sync {
+    fn evaluate(&self, ctx: &FieldContext) -> Consent;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OperatorWeight { pub operator: OperatorClass, pub weight: f64 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InfluenceEdge { pub from: OperatorClass, pub to: OperatorClass, pub relation: String, pub weight: f64 }
+
+/// Influence and negotiation signals derived from a run's resonance events.
+#[derive(Debug, Clone, Serialize)]
+pub struct InfluenceSnapshot {
+    pub resonance_index: f64,
+    pub operator_influence: Vec<OperatorWeight>,
+    pub cooperation_count: usize,
+    pub conflict_count: usize,
+    pub negotiation: Vec<InfluenceEdge>,
+    pub notes: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -93,6 +161,19 @@ pub struct ResonanceEvent {
     pub message: String,
     pub section_ref: Option<String>,
     pub symbol: Option<String>,
+    /// Stable rule code (e.g. `PHI001`) for events raised by an interaction rule.
+    pub code: Option<String>,
+    pub span: Option<SourceSpan>,
+    /// Machine-applicable fixes, most preferred first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl ResonanceEvent {
+    pub fn with_suggestion(mut self, s: Suggestion) -> Self {
+        self.suggestions.push(s);
+        self
+    }
 }
 
 pub trait MetaEngine: Send + Sync {
@@ -103,6 +184,8 @@ pub trait MetaEngine: Send + Sync {
         content: &str,
         ctx: &FieldContext,
     ) -> (Vec<ConstraintResult>, Vec<ResonanceEvent>);
+    /// Influence snapshot over events from `evaluate_meta`, for engines that compute one.
+    fn influence(&self, _events: &[ResonanceEvent]) -> Option<InfluenceSnapshot> { None }
     fn operators(&self) -> &[OperatorDef];
     fn conditionals(&self) -> &[ConditionalDef];
 }
@@ -130,7 +213,31 @@ pub struct GateOutcome {
     pub note: Option<String>,
 }
 
+/// Where one occurrence of an operator sits in the parsed expression.
+#[derive(Debug, Clone, Default)]
+pub struct GateContext<'a> {
+    pub content: &'a str,
+    pub span: Option<SourceSpan>,
+    /// Operand glyphs earlier in the same thread (threads split at `|`), nearest first.
+    pub preceding: Vec<String>,
+    /// Operand glyphs later in the same thread, nearest first.
+    pub following: Vec<String>,
+    /// Number of enclosing `[]` loop containers.
+    pub loop_depth: usize,
+    /// A juxtaposed neighbour is Φ, holding the operands on either side apart.
+    pub phi_boundary: bool,
+}
+
+impl<'a> GateContext<'a> {
+    /// Context with no neighbourhood, for content that was not parsed.
+    pub fn new(content: &'a str) -> Self { Self { content, ..Self::default() } }
+
+    pub fn preceded_by(&self, glyphs: &[&str]) -> bool { self.preceding.iter().any(|p| glyphs.contains(&p.as_str())) }
+
+    pub fn followed_by(&self, glyphs: &[&str]) -> bool { self.following.iter().any(|f| glyphs.contains(&f.as_str())) }
+}
+
 pub trait OperatorGate: Send + Sync {
     fn symbol(&self) -> &'static str;
-    fn apply(&self, content: &str) -> GateOutcome;
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome;
 }
@@ -0,0 +1,403 @@
+//! Recursive inoculation (primus §Π): guards against generated output being fed back as input
+//! without bound, and against prompts dense with self-reference (`Π`, deeply nested `[]`).
+//!
+//! A prompt's recursion depth is one more than that of the deepest earlier output it contains;
+//! fresh prompts have depth 0. Every admission decision leaves an [`InoculationRecord`].
+//!
+//! A guard lives as long as its process unless it keeps a journal
+//! ([`InoculationGuard::persistent`]): one JSON line per admission and per delivered output,
+//! replayed on load, so depth and repetition limits also hold across separate runs.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Section of the primus spec governing inoculation.
+pub const INOCULATION_SECTION: &str = "§Π";
+
+/// Earlier outputs, distinct prompts and records remembered.
+const HISTORY: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct InoculationLimits {
+    /// Feedback generations allowed on top of a fresh prompt.
+    pub max_depth: usize,
+    /// Times the same prompt may be admitted within `repetition_window_s`.
+    pub max_repetitions: usize,
+    /// Seconds an admission counts towards `max_repetitions`.
+    pub repetition_window_s: u64,
+    /// `[` nesting depth allowed in a prompt.
+    pub max_nesting: usize,
+    /// `Π` occurrences allowed in a prompt.
+    pub max_self_reference: usize,
+}
+
+impl Default for InoculationLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_repetitions: 3,
+            repetition_window_s: 3600,
+            max_nesting: 4,
+            max_self_reference: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InoculationBlock {
+    Depth { depth: usize, limit: usize },
+    Repetition { count: usize, limit: usize },
+    Nesting { depth: usize, limit: usize },
+    SelfReference { count: usize, limit: usize },
+}
+
+impl fmt::Display for InoculationBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InoculationBlock::Depth { depth, limit } => {
+                write!(f, "recursion depth {} exceeds {}", depth, limit)
+            }
+            InoculationBlock::Repetition { count, limit } => {
+                write!(f, "prompt repeated {} times, limit {}", count, limit)
+            }
+            InoculationBlock::Nesting { depth, limit } => {
+                write!(f, "bracket nesting {} exceeds {}", depth, limit)
+            }
+            InoculationBlock::SelfReference { count, limit } => {
+                write!(f, "{} Π self-references exceed {}", count, limit)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InoculationRecord {
+    pub modality: String,
+    pub depth: usize,
+    pub repetitions: usize,
+    pub nesting: usize,
+    pub self_reference: usize,
+    pub blocked: Option<InoculationBlock>,
+    /// Always [`INOCULATION_SECTION`]; not read back from journals.
+    #[serde(skip_deserializing, default = "section_ref")]
+    pub section_ref: &'static str,
+    pub timestamp_unix_s: u64,
+}
+
+fn section_ref() -> &'static str {
+    INOCULATION_SECTION
+}
+
+/// A journal line.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum JournalEntry {
+    Admission { prompt: String, record: InoculationRecord },
+    Output { content: String, depth: usize },
+}
+
+#[derive(Default)]
+struct GuardState {
+    /// Delivered outputs with the depth of the prompt that produced them.
+    outputs: Vec<(String, usize)>,
+    /// Admission times of each prompt, least recently admitted first. Blocked requests are
+    /// not admissions.
+    prompts: Vec<(String, Vec<u64>)>,
+    records: Vec<InoculationRecord>,
+}
+
+impl GuardState {
+    /// Admissions of `prompt` within `window_s` seconds before `now`.
+    fn admissions(&mut self, prompt: &str, now: u64, window_s: u64) -> usize {
+        let key = prompt.trim();
+        let Some((_, times)) = self.prompts.iter_mut().find(|(p, _)| p == key) else { return 0 };
+        times.retain(|t| t.saturating_add(window_s) > now);
+        times.len()
+    }
+
+    fn admitted(&mut self, prompt: &str, at: u64) {
+        let key = prompt.trim();
+        let mut times = match self.prompts.iter().position(|(p, _)| p == key) {
+            Some(i) => self.prompts.remove(i).1,
+            None => Vec::new(),
+        };
+        times.push(at);
+        push_capped(&mut self.prompts, (key.to_string(), times));
+    }
+
+    fn replay(&mut self, entry: JournalEntry) {
+        match entry {
+            JournalEntry::Admission { prompt, record } => {
+                if record.blocked.is_none() {
+                    self.admitted(&prompt, record.timestamp_unix_s);
+                }
+                push_capped(&mut self.records, record);
+            }
+            JournalEntry::Output { content, depth } => {
+                push_capped(&mut self.outputs, (content, depth))
+            }
+        }
+    }
+}
+
+fn push_capped<T>(items: &mut Vec<T>, item: T) {
+    if items.len() == HISTORY {
+        items.remove(0);
+    }
+    items.push(item);
+}
+
+#[derive(Default)]
+pub struct InoculationGuard {
+    limits: InoculationLimits,
+    state: Mutex<GuardState>,
+    journal: Option<PathBuf>,
+}
+
+impl InoculationGuard {
+    pub fn new(limits: InoculationLimits) -> Self {
+        Self { limits, state: Mutex::default(), journal: None }
+    }
+
+    /// A guard that replays the journal at `path` (a missing file is an empty journal) and
+    /// appends each admission and delivered output to it.
+    pub fn persistent(limits: InoculationLimits, path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut state = GuardState::default();
+        let src = match std::fs::read_to_string(&path) {
+            Ok(src) => src,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        for (i, line) in src.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let entry = serde_json::from_str(line).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", i + 1, e))
+            })?;
+            state.replay(entry);
+        }
+        Ok(Self { limits, state: Mutex::new(state), journal: Some(path) })
+    }
+
+    pub fn limits(&self) -> InoculationLimits {
+        self.limits
+    }
+
+    /// Checks `prompt` against the limits and records the decision; returns its depth.
+    pub fn admit(&self, modality: &str, prompt: &str) -> Result<usize, InoculationBlock> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        self.admit_at(modality, prompt, now)
+    }
+
+    /// [`admit`](Self::admit) at `now` (Unix seconds). Only admitted prompts count as
+    /// repetitions, and only within the limits' window.
+    pub fn admit_at(
+        &self,
+        modality: &str,
+        prompt: &str,
+        now: u64,
+    ) -> Result<usize, InoculationBlock> {
+        let mut state = self.state.lock().expect("inoculation state poisoned");
+        let depth = state
+            .outputs
+            .iter()
+            .filter(|(out, _)| !out.trim().is_empty() && prompt.contains(out.as_str()))
+            .map(|(_, d)| d + 1)
+            .max()
+            .unwrap_or(0);
+        let repetitions = state.admissions(prompt, now, self.limits.repetition_window_s) + 1;
+        let nesting = nesting_depth(prompt);
+        let self_reference = prompt.matches('Π').count();
+        let l = self.limits;
+        let blocked = if depth > l.max_depth {
+            Some(InoculationBlock::Depth { depth, limit: l.max_depth })
+        } else if repetitions > l.max_repetitions {
+            Some(InoculationBlock::Repetition { count: repetitions, limit: l.max_repetitions })
+        } else if nesting > l.max_nesting {
+            Some(InoculationBlock::Nesting { depth: nesting, limit: l.max_nesting })
+        } else if self_reference > l.max_self_reference {
+            Some(InoculationBlock::SelfReference {
+                count: self_reference,
+                limit: l.max_self_reference,
+            })
+        } else {
+            None
+        };
+        let record = InoculationRecord {
+            modality: modality.into(),
+            depth,
+            repetitions,
+            nesting,
+            self_reference,
+            blocked: blocked.clone(),
+            section_ref: INOCULATION_SECTION,
+            timestamp_unix_s: now,
+        };
+        info!("inoculation_record = {:?}", record);
+        self.journal(&JournalEntry::Admission {
+            prompt: prompt.to_string(),
+            record: record.clone(),
+        });
+        push_capped(&mut state.records, record);
+        if blocked.is_none() {
+            state.admitted(prompt, now);
+        }
+        blocked.map_or(Ok(depth), Err)
+    }
+
+    /// Remembers delivered output so feeding it back counts as one level deeper than `depth`.
+    pub fn record_output(&self, content: &str, depth: usize) {
+        let mut state = self.state.lock().expect("inoculation state poisoned");
+        self.journal(&JournalEntry::Output { content: content.to_string(), depth });
+        push_capped(&mut state.outputs, (content.to_string(), depth));
+    }
+
+    /// Audit records of the latest admission decisions, oldest first.
+    pub fn records(&self) -> Vec<InoculationRecord> {
+        self.state.lock().expect("inoculation state poisoned").records.clone()
+    }
+
+    /// Appends `entry` to the journal, if any. Called with the state locked, so lines keep
+    /// the order of the decisions.
+    fn journal(&self, entry: &JournalEntry) {
+        let Some(path) = &self.journal else { return };
+        let line = serde_json::to_string(entry).expect("journal entries serialize");
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut f| writeln!(f, "{}", line));
+        if let Err(e) = written {
+            warn!("inoculation_journal_error = {}: {}", path.display(), e);
+        }
+    }
+}
+
+fn nesting_depth(s: &str) -> usize {
+    let (mut depth, mut max) = (0usize, 0usize);
+    for c in s.chars() {
+        match c {
+            '[' => {
+                depth += 1;
+                max = max.max(depth);
+            }
+            ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feedback_depth_grows_until_blocked() {
+        let guard = InoculationGuard::new(InoculationLimits {
+            max_depth: 2,
+            ..InoculationLimits::default()
+        });
+        let mut prompt = "Ψ → Ω".to_string();
+        for expected in 0..=2 {
+            assert_eq!(guard.admit("text", &prompt), Ok(expected));
+            prompt = format!("TEXT: {}", prompt);
+            guard.record_output(&prompt, expected);
+        }
+        assert_eq!(
+            guard.admit("text", &prompt),
+            Err(InoculationBlock::Depth { depth: 3, limit: 2 })
+        );
+        let records = guard.records();
+        assert_eq!(records.len(), 4);
+        assert!(records[3].blocked.is_some() && records[3].section_ref == "§Π");
+    }
+
+    #[test]
+    fn repetition_and_self_reference_limits() {
+        let guard = InoculationGuard::new(InoculationLimits {
+            max_repetitions: 1,
+            ..InoculationLimits::default()
+        });
+        assert!(guard.admit("text", "Ψ").is_ok());
+        assert_eq!(
+            guard.admit("text", " Ψ "),
+            Err(InoculationBlock::Repetition { count: 2, limit: 1 })
+        );
+        assert_eq!(
+            guard.admit("text", "[[[[[Ψ]]]]]"),
+            Err(InoculationBlock::Nesting { depth: 5, limit: 4 })
+        );
+        assert_eq!(
+            guard.admit("text", "Π Π Π Π Π"),
+            Err(InoculationBlock::SelfReference { count: 5, limit: 4 })
+        );
+    }
+
+    #[test]
+    fn repetitions_expire_and_blocks_do_not_count() {
+        let guard = InoculationGuard::new(InoculationLimits {
+            max_repetitions: 2,
+            repetition_window_s: 100,
+            ..InoculationLimits::default()
+        });
+        assert_eq!(guard.admit_at("text", "Ψ", 1000), Ok(0));
+        assert_eq!(guard.admit_at("text", "Ψ", 1010), Ok(0));
+        for _ in 0..3 {
+            let blocked = guard.admit_at("text", "Ψ", 1020);
+            assert_eq!(blocked, Err(InoculationBlock::Repetition { count: 3, limit: 2 }));
+        }
+        // The first admission has left the window; the blocked attempts never counted.
+        assert_eq!(guard.admit_at("text", "Ψ", 1100), Ok(0));
+        assert!(guard.admit_at("text", "Ψ", 1101).is_err());
+        assert_eq!(guard.admit_at("text", "Ψ", 1300), Ok(0));
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let guard = InoculationGuard::default();
+        for i in 0..HISTORY + 10 {
+            assert!(guard.admit("text", &format!("Ψ {}", i)).is_ok());
+        }
+        let state = guard.state.lock().unwrap();
+        assert_eq!((state.prompts.len(), state.records.len()), (HISTORY, HISTORY));
+        assert_eq!(state.prompts[0].0, "Ψ 10");
+    }
+
+    #[test]
+    fn journal_carries_limits_across_guards() {
+        let path =
+            std::env::temp_dir().join(format!("ri1-inoculation-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let limits =
+            InoculationLimits { max_depth: 1, max_repetitions: 1, ..InoculationLimits::default() };
+        let first = InoculationGuard::persistent(limits, &path).unwrap();
+        assert_eq!(first.admit("text", "Ψ → Ω"), Ok(0));
+        first.record_output("TEXT: Ψ → Ω", 0);
+
+        let second = InoculationGuard::persistent(limits, &path).unwrap();
+        assert_eq!(
+            second.admit("text", "Ψ → Ω"),
+            Err(InoculationBlock::Repetition { count: 2, limit: 1 })
+        );
+        assert_eq!(second.admit("text", "TEXT: Ψ → Ω"), Ok(1));
+        second.record_output("TEXT: TEXT: Ψ → Ω", 1);
+
+        let third = InoculationGuard::persistent(limits, &path).unwrap();
+        assert_eq!(
+            third.admit("text", "TEXT: TEXT: Ψ → Ω"),
+            Err(InoculationBlock::Depth { depth: 2, limit: 1 })
+        );
+        // Blocked decisions are journaled too.
+        let records = InoculationGuard::persistent(limits, &path).unwrap().records();
+        assert_eq!(records.iter().filter(|r| r.blocked.is_some()).count(), 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
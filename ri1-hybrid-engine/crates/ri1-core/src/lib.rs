@@ -1,5 +1,7 @@
 pub mod orchestrator;
 pub mod modality;
 pub mod constraints;
+pub mod inoculation;
 
-pub use orchestrator::Orchestrator;
+pub use inoculation::{InoculationBlock, InoculationGuard, InoculationLimits, InoculationRecord};
+pub use orchestrator::{GenerationReport, Orchestrator, OrchestratorError, Timings};
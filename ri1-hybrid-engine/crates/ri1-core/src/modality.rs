@@ -1,8 +1,16 @@
 use serde::Serialize;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct GenerationRequest {
     pub prompt: String,
+    /// Carried into the generation report; generated when absent.
+    pub correlation_id: Option<String>,
+}
+
+impl GenerationRequest {
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self { prompt: prompt.into(), correlation_id: None }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
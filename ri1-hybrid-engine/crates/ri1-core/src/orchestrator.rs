@@ -1,21 +1,29 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
+use std::time::Instant;
+use serde::Serialize;
 use tracing::{info, warn};
+use uuid::Uuid;
 
-use crate::modality::{GenerationRequest, GenerationResponse, Modality};
-use crate::constraints::{ConstraintEngine, MetaEngine, Severity, ConstraintResult, ResonanceEvent, FieldContext};
+use crate::inoculation::{InoculationBlock, InoculationGuard, InoculationRecord};
+use crate::modality::{GenerationRequest, Modality};
+use crate::constraints::{Consent, ConstraintEngine, InfluenceSnapshot, MetaEngine, Severity, ConstraintResult, ResonanceEvent, FieldContext};
 
 pub struct Orchestrator {
     registry: HashMap<&'static str, Arc<dyn Modality>>, // name -> modality
     constraint_engine: Option<Arc<dyn ConstraintEngine>>, // legacy engine
     meta_engine: Option<Arc<dyn MetaEngine>>,             // preferred meta engine
-    last_meta_log: Vec<ResonanceEvent>,
-    last_results: Vec<ConstraintResult>,
+    inoculation: Option<InoculationGuard>,
+}
+
+impl Default for Orchestrator {
+    fn default() -> Self { Self::new() }
 }
 
 impl Orchestrator {
     pub fn new() -> Self {
-        Self { registry: HashMap::new(), constraint_engine: None, meta_engine: None, last_meta_log: Vec::new(), last_results: Vec::new() }
+        Self { registry: HashMap::new(), constraint_engine: None, meta_engine: None, inoculation: None }
     }
 
     pub fn register_modality_arc<M: Modality + 'static>(&mut self, m: Arc<M>) {
@@ -42,9 +50,25 @@ impl Orchestrator {
         info!("meta_engine = set");
     }
 
+    /// Checks every prompt against recursion and self-reference limits before generating.
+    pub fn set_inoculation_guard(&mut self, guard: InoculationGuard) {
+        self.inoculation = Some(guard);
+        info!("inoculation_guard = set");
+    }
+
+    pub fn inoculation_records(&self) -> Vec<InoculationRecord> {
+        self.inoculation.as_ref().map(InoculationGuard::records).unwrap_or_default()
+    }
+
     pub fn evaluate(&self, modality: &str, content: &str) -> Vec<ConstraintResult> {
+        self.evaluate_in_context(modality, content, &FieldContext::default())
+    }
+
+    /// [`evaluate`](Self::evaluate) in the given field context; its `modality` defaults to
+    /// `modality`.
+    pub fn evaluate_in_context(&self, modality: &str, content: &str, ctx: &FieldContext) -> Vec<ConstraintResult> {
         if let Some(engine) = &self.meta_engine {
-            let ctx = FieldContext::default();
+            let ctx = request_context(modality, ctx);
             let (_results, _res_log) = engine.evaluate_meta(modality, content, &ctx);
             return _results;
         }
@@ -55,8 +79,12 @@ impl Orchestrator {
     }
 
     pub fn evaluate_with_events(&self, modality: &str, content: &str) -> (Vec<ConstraintResult>, Vec<ResonanceEvent>) {
+        self.evaluate_with_events_in_context(modality, content, &FieldContext::default())
+    }
+
+    pub fn evaluate_with_events_in_context(&self, modality: &str, content: &str, ctx: &FieldContext) -> (Vec<ConstraintResult>, Vec<ResonanceEvent>) {
         if let Some(engine) = &self.meta_engine {
-            let ctx = FieldContext::default();
+            let ctx = request_context(modality, ctx);
             return engine.evaluate_meta(modality, content, &ctx);
         }
         match &self.constraint_engine {
@@ -64,44 +92,121 @@ impl Orchestrator {
             None => (Vec::new(), Vec::new()),
         }
     }
-    pub fn last_meta_events(&self) -> &Vec<ResonanceEvent> { &self.last_meta_log }
-
-    pub fn generate(&self, modality: &str, req: GenerationRequest) -> Option<GenerationResponse> {
-        match self.registry.get(modality) {
-            Some(m) => {
-                let out = m.generate(req);
-                // Prefer meta engine: consent check + evaluate + block on hard failures
-                if let Some(engine) = &self.meta_engine {
-                    let ctx = FieldContext::default();
-                    let consent = engine.consent_check(&ctx);
-                    if !consent.granted {
-                        warn!("generation_blocked_by_consent");
-                        return None;
-                    }
-                    let (results, events) = engine.evaluate_meta(modality, &out.content, &ctx);
-                    let hard_fail = results.iter().any(|r| !r.passed && r.severity == Severity::Hard);
-                    if hard_fail {
-                        warn!("generation_blocked_by_hard_constraint");
-                        return None;
-                    }
-                    // store logs
-                    // NOTE: using interior mutability would avoid &self, but keep simple for now by shadowing
-                    // (we won't expose mutation of logs here to keep API simple in Phase 2 scaffold)
-                } else if let Some(engine) = &self.constraint_engine {
-                    let results = engine.evaluate(modality, &out.content);
-                    let hard_fail = results.iter().any(|r| !r.passed && r.severity == Severity::Hard);
-                    if hard_fail {
-                        warn!("generation_blocked_by_hard_constraint");
-                        return None;
-                    }
+
+    pub fn generate(&self, modality: &str, req: GenerationRequest) -> Result<GenerationReport, OrchestratorError> {
+        self.generate_in_context(modality, req, &FieldContext::default())
+    }
+
+    /// [`generate`](Self::generate) with consent and evaluation in the given field context.
+    pub fn generate_in_context(&self, modality: &str, req: GenerationRequest, ctx: &FieldContext) -> Result<GenerationReport, OrchestratorError> {
+        let started = Instant::now();
+        let Some(m) = self.registry.get(modality) else {
+            warn!("unknown_modality = {}", modality);
+            return Err(OrchestratorError::UnknownModality(modality.into()));
+        };
+        let ctx = request_context(modality, ctx);
+        if let Some(engine) = &self.meta_engine {
+            let consent = engine.consent_check(&ctx);
+            if !consent.granted {
+                warn!("generation_blocked_by_consent = {}", consent.reason.as_deref().unwrap_or("-"));
+                return Err(OrchestratorError::ConsentDenied(consent));
+            }
+        }
+        // Admitted only after consent, so denied requests do not count as repetitions.
+        let depth = match self.inoculation.as_ref().map(|g| g.admit(modality, &req.prompt)).transpose() {
+            Ok(depth) => depth,
+            Err(block) => {
+                warn!("generation_blocked_by_inoculation = {}", block);
+                return Err(OrchestratorError::Inoculation(block));
+            }
+        };
+        let correlation_id = req.correlation_id.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
+        let generating = Instant::now();
+        let out = m.generate(req);
+        let generation_us = generating.elapsed().as_micros() as u64;
+        // Prefer meta engine: evaluate + block on hard failures
+        let evaluating = Instant::now();
+        let (results, events, influence) = match (&self.meta_engine, &self.constraint_engine) {
+            (Some(engine), _) => {
+                let (results, events) = engine.evaluate_meta(modality, &out.content, &ctx);
+                let influence = engine.influence(&events);
+                (results, events, influence)
+            }
+            (None, Some(engine)) => (engine.evaluate(modality, &out.content), Vec::new(), None),
+            (None, None) => (Vec::new(), Vec::new(), None),
+        };
+        let evaluation_us = evaluating.elapsed().as_micros() as u64;
+        let failures: Vec<_> = results.iter().filter(|r| !r.passed && r.severity == Severity::Hard).cloned().collect();
+        if !failures.is_empty() {
+            warn!("generation_blocked_by_hard_constraint");
+            return Err(OrchestratorError::HardConstraints { modality: modality.into(), failures });
+        }
+        if let (Some(guard), Some(depth)) = (&self.inoculation, depth) {
+            guard.record_output(&out.content, depth);
+        }
+        let timings = Timings { generation_us, evaluation_us, total_us: started.elapsed().as_micros() as u64 };
+        Ok(GenerationReport { correlation_id, modality: modality.into(), content: out.content, results, events, influence, timings })
+    }
+}
+
+/// Wall-clock time spent on a generation, in microseconds.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Timings {
+    pub generation_us: u64,
+    pub evaluation_us: u64,
+    /// From the request to the report, including admission and consent checks.
+    pub total_us: u64,
+}
+
+/// Generated content with everything its evaluation produced.
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationReport {
+    pub correlation_id: String,
+    pub modality: String,
+    pub content: String,
+    pub results: Vec<ConstraintResult>,
+    pub events: Vec<ResonanceEvent>,
+    /// Present when the meta engine computes one.
+    pub influence: Option<InfluenceSnapshot>,
+    pub timings: Timings,
+}
+
+/// Why [`Orchestrator::generate`] produced nothing.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum OrchestratorError {
+    UnknownModality(String),
+    Inoculation(InoculationBlock),
+    ConsentDenied(Consent),
+    /// The generated content failed these hard constraints.
+    HardConstraints { modality: String, failures: Vec<ConstraintResult> },
+}
+
+impl fmt::Display for OrchestratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrchestratorError::UnknownModality(m) => write!(f, "unknown modality '{}'", m),
+            OrchestratorError::Inoculation(block) => write!(f, "blocked by recursive inoculation: {}", block),
+            OrchestratorError::ConsentDenied(c) => {
+                write!(f, "consent denied")?;
+                if let Some(subject) = &c.subject {
+                    write!(f, " for {}", subject)?;
+                }
+                if let Some(reason) = &c.reason {
+                    write!(f, ": {}", reason)?;
                 }
-                Some(out)
+                Ok(())
             }
-            None => {
-                warn!("unknown_modality = {}", modality);
-                None
+            OrchestratorError::HardConstraints { modality, failures } => {
+                let names: Vec<_> = failures.iter().map(|r| r.name).collect();
+                write!(f, "{} output failed hard constraints: {}", modality, names.join(", "))
             }
         }
     }
 }
 
+impl std::error::Error for OrchestratorError {}
+
+fn request_context(modality: &str, ctx: &FieldContext) -> FieldContext {
+    FieldContext { modality: ctx.modality.clone().or_else(|| Some(modality.into())), ..ctx.clone() }
+}
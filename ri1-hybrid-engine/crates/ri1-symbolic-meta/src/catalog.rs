@@ -0,0 +1,430 @@
+//! The Φπε operator catalog: one entry per symbol. The engine's `OperatorDef`s, its
+//! [`GateRegistry`](crate::GateRegistry) bindings and the section refs its events cite are all
+//! derived from a [`Catalog`], either the built-in [`OPERATORS`]/[`CONDITIONALS`] or a spec file
+//! following the `docs/spec/phipe-spec.md` template:
+//!
+//! ```toml
+//! [[operators]]
+//! symbol = "Φ"
+//! name = "HarmonicStabilization"
+//! class = "HarmonicStabilization"
+//! section = "001"
+//! properties = "non-fusional, non-neutralizing"
+//! source = "RI1_LANGUAGE_Φπε_PROOFS (1).pdf"
+//!
+//! [[conditionals]]
+//! symbol = "→"
+//! name = "FlowVector"
+//! class = "FlowVector"
+//! description = "→: flow vector — directed recursion motion"
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use ri1_core::constraints::{ConditionalDef, OperatorClass, OperatorDef};
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::de::IntoDeserializer;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorEntry {
+    pub key: &'static str,
+    pub symbol: &'static str,
+    /// Other glyphs read as this operator, e.g. `ρ` for `Ρ`.
+    pub aliases: &'static [&'static str],
+    /// ASCII spelling, also accepted LaTeX-style with a leading `\`.
+    pub ascii: Option<&'static str>,
+    pub class: OperatorClass,
+    pub section_ref: Option<&'static str>,
+    /// A built-in gate judges the symbol. `n` is detected as an index modifier instead.
+    pub gated: bool,
+}
+
+impl OperatorEntry {
+    /// The symbol followed by its aliases.
+    pub fn glyphs(&self) -> impl Iterator<Item = &'static str> {
+        std::iter::once(self.symbol).chain(self.aliases.iter().copied())
+    }
+}
+
+const fn op(
+    key: &'static str,
+    symbol: &'static str,
+    ascii: &'static str,
+    class: OperatorClass,
+    section_ref: &'static str,
+) -> OperatorEntry {
+    OperatorEntry {
+        key,
+        symbol,
+        aliases: &[],
+        ascii: Some(ascii),
+        class,
+        section_ref: Some(section_ref),
+        gated: true,
+    }
+}
+
+/// Φπε operators, in gate evaluation order.
+pub const OPERATORS: &[OperatorEntry] = {
+    use OperatorClass as C;
+    &[
+        op("MeasurementPerceptionBridge", "χ", "chi", C::MeasurementBridge, "020"),
+        op("IgnitionInitiation", "Ε", "Epsilon", C::Ignition, "019"),
+        op("HarmonicStabilization", "Φ", "Phi", C::HarmonicStabilization, "001"),
+        op("ClosureIntegration", "Ω", "Omega", C::ClosureIntegration, "004"),
+        op("Oscillation", "Ψ", "Psi", C::Oscillation, "008"),
+        OperatorEntry {
+            aliases: &["ρ"],
+            ..op("PerceptionModulation", "Ρ", "Rho", C::PerceptionModulation, "014")
+        },
+        OperatorEntry {
+            aliases: &["τ"],
+            ..op("SynchronicityReadiness", "Τ", "Tau", C::Synchronicity, "018")
+        },
+        op("IntentionVector", "Θ", "Theta", C::IntentionVector, "016"),
+        op("MicroIgnition", "ε", "epsilon", C::MicroIgnition, "010"),
+        op("WillForce", "ω", "omega", C::WillForce, "013"),
+        op("MicroTransformation", "δ", "delta", C::MicroTransformation, "015"),
+        op("FusionTransformation", "Δ", "Delta", C::Fusion, "002"),
+        op("StructuralIllumination", "Λ", "Lambda", C::StructuralIllumination, "003"),
+        op("DirectionalGrowth", "Γ", "Gamma", C::DirectionalGrowth, "007"),
+        op("RecursiveGrowth", "Γ̇", "Gamma.dot", C::DirectionalGrowth, "007"),
+        op("EmergentSystem", "Ξ", "Xi", C::EmergentSystem, "006"),
+        op("RecurrencePatternEcho", "ζ", "zeta", C::RecurrencePattern, "011"),
+        op("Entanglement", "λ", "lambda", C::Entanglement, "012"),
+        op("CoexistencePlurality", "Σ", "Sigma", C::Coexistence, "005"),
+        op("TranscendentContinuity", "Π", "Pi", C::Transcendence, "009"),
+        OperatorEntry {
+            aliases: &["ⁿ"],
+            ascii: None,
+            gated: false,
+            ..op("DepthIndexModifier", "n", "n", C::IndexModifier, "017")
+        },
+    ]
+};
+
+/// The entry for `glyph`, by symbol or alias.
+pub fn entry(glyph: &str) -> Option<&'static OperatorEntry> {
+    OPERATORS.iter().find(|e| e.glyphs().any(|g| g == glyph))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConditionalEntry {
+    pub key: &'static str,
+    pub symbol: &'static str,
+    pub ascii: Option<&'static str>,
+    pub class: OperatorClass,
+    pub description: &'static str,
+}
+
+/// Φπε conditionals. `[]` is reported when both brackets occur.
+pub const CONDITIONALS: &[ConditionalEntry] = {
+    use OperatorClass as C;
+    const fn cond(
+        key: &'static str,
+        symbol: &'static str,
+        class: OperatorClass,
+        description: &'static str,
+    ) -> ConditionalEntry {
+        ConditionalEntry { key, symbol, ascii: None, class, description }
+    }
+    &[
+        ConditionalEntry {
+            ascii: Some("->"),
+            ..cond("FlowVector", "→", C::FlowVector, "→: flow vector — directed recursion motion")
+        },
+        cond("Simultaneity", "+", C::Simultaneity, "+: simultaneity — coexistent recursion states"),
+        cond(
+            "InteractionInterface",
+            ":",
+            C::InteractionInterface,
+            ": interaction — relational interface / tension-contact",
+        ),
+        cond("Disruption", "/", C::Disruption, "/: disruption — interference / rupture"),
+        cond("Orthogonality", "|", C::Orthogonality, "|: orthogonality — non-interacting fields"),
+        cond(
+            "LoopCycle",
+            "[]",
+            C::LoopCycle,
+            "[]: loop/cycle — recursion memory or repeat-phase container",
+        ),
+        cond(
+            "StabilizationResolution",
+            "=",
+            C::StabilizationResolution,
+            "=: stabilization — final form / resolution",
+        ),
+    ]
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorSpec {
+    pub key: String,
+    pub symbol: String,
+    pub aliases: Vec<String>,
+    pub ascii: Option<String>,
+    pub class: OperatorClass,
+    pub section_ref: Option<String>,
+    /// Bind the built-in gate for `symbol`.
+    pub gated: bool,
+    pub properties: Option<String>,
+    pub constraints: Option<String>,
+    pub rust: Option<String>,
+    pub source: Option<String>,
+}
+
+impl OperatorSpec {
+    /// The symbol followed by its aliases.
+    pub fn glyphs(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.symbol.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionalSpec {
+    pub key: String,
+    pub symbol: String,
+    pub ascii: Option<String>,
+    pub class: OperatorClass,
+    pub section_ref: Option<String>,
+    /// Message of the event raised when the conditional occurs.
+    pub description: String,
+}
+
+/// Operators and conditionals the engine recognises.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    pub operators: Vec<OperatorSpec>,
+    pub conditionals: Vec<ConditionalSpec>,
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    /// A symbol, alias or name listed twice.
+    Duplicate(String),
+    UnknownClass {
+        symbol: String,
+        class: String,
+    },
+    /// `gate = true` for a symbol without a built-in gate.
+    NoGate(String),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io(e) => write!(f, "cannot read catalog: {}", e),
+            CatalogError::Toml(e) => write!(f, "invalid catalog: {}", e),
+            CatalogError::Json(e) => write!(f, "invalid catalog: {}", e),
+            CatalogError::Duplicate(s) => write!(f, "'{}' is listed more than once", s),
+            CatalogError::UnknownClass { symbol, class } => {
+                write!(f, "unknown operator class '{}' for '{}'", class, symbol)
+            }
+            CatalogError::NoGate(s) => write!(f, "no built-in gate for '{}'; set gate = false", s),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    operators: Vec<OperatorRecord>,
+    #[serde(default)]
+    conditionals: Vec<ConditionalRecord>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OperatorRecord {
+    symbol: String,
+    name: String,
+    class: String,
+    section: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    ascii: Option<String>,
+    #[serde(default = "gated_by_default")]
+    gate: bool,
+    properties: Option<String>,
+    constraints: Option<String>,
+    rust: Option<String>,
+    source: Option<String>,
+}
+
+fn gated_by_default() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConditionalRecord {
+    symbol: String,
+    name: String,
+    class: String,
+    section: Option<String>,
+    ascii: Option<String>,
+    description: Option<String>,
+}
+
+fn class(symbol: &str, name: &str) -> Result<OperatorClass, CatalogError> {
+    let de: StrDeserializer<'_, ValueError> = name.into_deserializer();
+    OperatorClass::deserialize(de)
+        .map_err(|_| CatalogError::UnknownClass { symbol: symbol.into(), class: name.into() })
+}
+
+impl Catalog {
+    pub fn builtin() -> Self {
+        let operators = OPERATORS
+            .iter()
+            .map(|e| OperatorSpec {
+                key: e.key.into(),
+                symbol: e.symbol.into(),
+                aliases: e.aliases.iter().map(|a| a.to_string()).collect(),
+                ascii: e.ascii.map(String::from),
+                class: e.class,
+                section_ref: e.section_ref.map(String::from),
+                gated: e.gated,
+                properties: None,
+                constraints: None,
+                rust: None,
+                source: None,
+            })
+            .collect();
+        let conditionals = CONDITIONALS
+            .iter()
+            .map(|c| ConditionalSpec {
+                key: c.key.into(),
+                symbol: c.symbol.into(),
+                ascii: c.ascii.map(String::from),
+                class: c.class,
+                section_ref: None,
+                description: c.description.into(),
+            })
+            .collect();
+        Self { operators, conditionals }
+    }
+
+    pub fn from_toml_str(src: &str) -> Result<Self, CatalogError> {
+        Self::from_file(toml::from_str(src).map_err(CatalogError::Toml)?)
+    }
+
+    pub fn from_json_str(src: &str) -> Result<Self, CatalogError> {
+        Self::from_file(serde_json::from_str(src).map_err(CatalogError::Json)?)
+    }
+
+    /// Reads a `.json` file as JSON and anything else as TOML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let path = path.as_ref();
+        let src = std::fs::read_to_string(path).map_err(CatalogError::Io)?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json_str(&src),
+            _ => Self::from_toml_str(&src),
+        }
+    }
+
+    fn from_file(file: CatalogFile) -> Result<Self, CatalogError> {
+        let mut operators = Vec::new();
+        for r in file.operators {
+            operators.push(OperatorSpec {
+                class: class(&r.symbol, &r.class)?,
+                key: r.name,
+                symbol: r.symbol,
+                aliases: r.aliases,
+                ascii: r.ascii,
+                section_ref: r.section,
+                gated: r.gate,
+                properties: r.properties,
+                constraints: r.constraints,
+                rust: r.rust,
+                source: r.source,
+            });
+        }
+        let mut conditionals = Vec::new();
+        for r in file.conditionals {
+            conditionals.push(ConditionalSpec {
+                class: class(&r.symbol, &r.class)?,
+                description: r.description.unwrap_or_else(|| format!("{}: {}", r.symbol, r.name)),
+                key: r.name,
+                symbol: r.symbol,
+                ascii: r.ascii,
+                section_ref: r.section,
+            });
+        }
+        let catalog = Self { operators, conditionals };
+        catalog.check_unique()?;
+        Ok(catalog)
+    }
+
+    /// Every glyph, ASCII spelling and name may appear once across operators and conditionals.
+    fn check_unique(&self) -> Result<(), CatalogError> {
+        let mut glyphs = HashSet::new();
+        let mut names = HashSet::new();
+        let ops = self
+            .operators
+            .iter()
+            .map(|o| (o.key.as_str(), o.glyphs().chain(o.ascii.as_deref()).collect::<Vec<_>>()));
+        let conds = self.conditionals.iter().map(|c| {
+            (c.key.as_str(), std::iter::once(c.symbol.as_str()).chain(c.ascii.as_deref()).collect())
+        });
+        for (name, gs) in ops.chain(conds) {
+            if !names.insert(name) {
+                return Err(CatalogError::Duplicate(name.into()));
+            }
+            if let Some(g) = gs.into_iter().find(|g| !glyphs.insert(*g)) {
+                return Err(CatalogError::Duplicate(g.into()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The operator for `glyph`, by symbol or alias.
+    pub fn operator(&self, glyph: &str) -> Option<&OperatorSpec> {
+        self.operators.iter().find(|o| o.glyphs().any(|g| g == glyph))
+    }
+
+    /// The glyph spelled `ascii`, for operators and conditionals.
+    pub fn glyph_for_ascii(&self, ascii: &str) -> Option<&str> {
+        let ops = self.operators.iter().map(|o| (o.ascii.as_deref(), o.symbol.as_str()));
+        let conds = self.conditionals.iter().map(|c| (c.ascii.as_deref(), c.symbol.as_str()));
+        ops.chain(conds).find(|(a, _)| *a == Some(ascii)).map(|(_, g)| g)
+    }
+
+    /// ASCII spelling of `glyph`, an operator (by symbol or alias) or conditional.
+    pub fn ascii_for(&self, glyph: &str) -> Option<&str> {
+        let cond = || {
+            self.conditionals.iter().find(|c| c.symbol == glyph).and_then(|c| c.ascii.as_deref())
+        };
+        self.operator(glyph).and_then(|o| o.ascii.as_deref()).or_else(cond)
+    }
+
+    pub fn operator_defs(&self) -> Vec<OperatorDef> {
+        self.operators
+            .iter()
+            .map(|o| OperatorDef {
+                key: o.key.clone(),
+                symbol: o.symbol.clone(),
+                section_ref: o.section_ref.clone(),
+            })
+            .collect()
+    }
+
+    pub fn conditional_defs(&self) -> Vec<ConditionalDef> {
+        self.conditionals
+            .iter()
+            .map(|c| ConditionalDef {
+                key: c.key.clone(),
+                symbol: c.symbol.clone(),
+                section_ref: c.section_ref.clone(),
+            })
+            .collect()
+    }
+}
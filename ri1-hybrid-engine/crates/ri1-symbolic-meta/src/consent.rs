@@ -0,0 +1,228 @@
+//! Consent policies behind `MetaEngine::consent_check`. Every decision cites the primus
+//! consent section, `§Π`, unless a policy rule names a narrower one.
+//!
+//! A [`DeclarativePolicy`] is a TOML file of rules tried in order; the first whose fields all
+//! match the [`FieldContext`] decides, and `default` applies when none does:
+//!
+//! ```toml
+//! default = "deny"
+//!
+//! [[rule]]
+//! effect = "deny"
+//! phase = "omega"
+//! reason = "closed fields take no new generations"
+//!
+//! [[rule]]
+//! effect = "grant"
+//! subject = ["alice", "team-*"]
+//! modality = "text"
+//! ```
+//!
+//! A field pattern is an exact value, `*` for any value, or `prefix*`; a list matches any of
+//! its patterns. A rule that names a field never matches a context without that field.
+//!
+//! Recorded grants live in the [`ledger`], which the engine consults after its policy.
+
+use std::fmt;
+use std::path::Path;
+
+use ri1_core::constraints::{Consent, ConsentPolicy, FieldContext};
+use serde::Deserialize;
+
+pub mod ledger;
+
+/// Section of the primus spec governing consent.
+pub const PRIMUS_CONSENT_REF: &str = "§Π";
+
+/// Grants every request; the engine's policy until another is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrantAll;
+
+impl ConsentPolicy for GrantAll {
+    fn evaluate(&self, ctx: &FieldContext) -> Consent {
+        Consent {
+            granted: true,
+            subject: Some(ctx.subject.clone().unwrap_or_else(|| "default".into())),
+            reason: None,
+            section_ref: Some(PRIMUS_CONSENT_REF.into()),
+            ledger_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Effect {
+    Grant,
+    Deny,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Pattern {
+    One(String),
+    AnyOf(Vec<String>),
+}
+
+impl Pattern {
+    pub fn matches(&self, value: Option<&str>) -> bool {
+        let Some(value) = value else { return false };
+        let one = |p: &String| match p.strip_suffix('*') {
+            Some(prefix) => value.starts_with(prefix),
+            None => p == value,
+        };
+        match self {
+            Pattern::One(p) => one(p),
+            Pattern::AnyOf(ps) => ps.iter().any(one),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    pub effect: Effect,
+    pub subject: Option<Pattern>,
+    pub phase: Option<Pattern>,
+    pub source: Option<Pattern>,
+    pub field_id: Option<Pattern>,
+    pub modality: Option<Pattern>,
+    pub reason: Option<String>,
+    /// Overrides [`PRIMUS_CONSENT_REF`] for decisions made by this rule.
+    pub section_ref: Option<String>,
+}
+
+impl PolicyRule {
+    pub fn matches(&self, ctx: &FieldContext) -> bool {
+        let fields = [
+            (&self.subject, &ctx.subject),
+            (&self.phase, &ctx.phase),
+            (&self.source, &ctx.source),
+            (&self.field_id, &ctx.field_id),
+            (&self.modality, &ctx.modality),
+        ];
+        fields.iter().all(|(p, v)| p.as_ref().is_none_or(|p| p.matches(v.as_deref())))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeclarativePolicy {
+    /// Effect when no rule matches; deny unless stated.
+    #[serde(default = "deny_by_default")]
+    pub default: Effect,
+    pub default_reason: Option<String>,
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<PolicyRule>,
+}
+
+fn deny_by_default() -> Effect {
+    Effect::Deny
+}
+
+#[derive(Debug)]
+pub enum PolicyError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::Io(e) => write!(f, "cannot read consent policy: {}", e),
+            PolicyError::Parse(e) => write!(f, "invalid consent policy: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl DeclarativePolicy {
+    pub fn from_toml_str(src: &str) -> Result<Self, PolicyError> {
+        toml::from_str(src).map_err(PolicyError::Parse)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PolicyError> {
+        let src = std::fs::read_to_string(path).map_err(PolicyError::Io)?;
+        Self::from_toml_str(&src)
+    }
+}
+
+impl ConsentPolicy for DeclarativePolicy {
+    fn evaluate(&self, ctx: &FieldContext) -> Consent {
+        let decide = |effect: Effect, reason: String, section_ref: Option<&String>| Consent {
+            granted: effect == Effect::Grant,
+            subject: ctx.subject.clone(),
+            reason: Some(reason),
+            section_ref: Some(section_ref.map_or(PRIMUS_CONSENT_REF, String::as_str).into()),
+            ledger_id: None,
+        };
+        match self.rules.iter().enumerate().find(|(_, r)| r.matches(ctx)) {
+            Some((i, r)) => {
+                let reason = r.reason.clone().unwrap_or_else(|| format!("policy rule {}", i + 1));
+                decide(r.effect, reason, r.section_ref.as_ref())
+            }
+            None => decide(
+                self.default,
+                self.default_reason.clone().unwrap_or_else(|| "no policy rule matched".into()),
+                None,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(subject: Option<&str>, phase: &str) -> FieldContext {
+        FieldContext {
+            subject: subject.map(String::from),
+            phase: Some(phase.into()),
+            modality: Some("text".into()),
+            ..FieldContext::default()
+        }
+    }
+
+    const POLICY: &str = r#"
+        [[rule]]
+        effect = "deny"
+        phase = "omega"
+        reason = "closed field"
+        section_ref = "§Π.2"
+
+        [[rule]]
+        effect = "grant"
+        subject = ["alice", "team-*"]
+        modality = "text"
+    "#;
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let p = DeclarativePolicy::from_toml_str(POLICY).unwrap();
+        let c = p.evaluate(&ctx(Some("alice"), "omega"));
+        assert!(!c.granted);
+        assert_eq!(
+            (c.reason.as_deref(), c.section_ref.as_deref()),
+            (Some("closed field"), Some("§Π.2"))
+        );
+        let c = p.evaluate(&ctx(Some("team-red"), "alpha"));
+        assert!(c.granted);
+        assert_eq!(
+            (c.reason.as_deref(), c.section_ref.as_deref()),
+            (Some("policy rule 2"), Some("§Π"))
+        );
+    }
+
+    #[test]
+    fn unmatched_contexts_fall_back_to_default() {
+        let p = DeclarativePolicy::from_toml_str(POLICY).unwrap();
+        // A rule naming `subject` does not match an anonymous context.
+        let c = p.evaluate(&ctx(None, "alpha"));
+        assert!(!c.granted);
+        assert_eq!(c.reason.as_deref(), Some("no policy rule matched"));
+        let open = DeclarativePolicy::from_toml_str("default = \"grant\"").unwrap();
+        assert!(open.evaluate(&ctx(None, "alpha")).granted);
+        assert!(DeclarativePolicy::from_toml_str("[[rule]]\neffect = \"maybe\"").is_err());
+    }
+}
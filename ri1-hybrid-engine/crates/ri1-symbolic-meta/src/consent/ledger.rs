@@ -0,0 +1,319 @@
+//! Append-only consent ledger: one JSON entry per line, grants and the revocations that end
+//! them. Entries are never rewritten, so the file is the audit trail of who consented to what.
+//!
+//! A grant covers a subject for a set of scopes (modality patterns as in policy rules; none
+//! means every modality) until its optional expiry. As a [`ConsentPolicy`] the ledger grants a
+//! request when the context's subject (`default` when unset) holds a live grant for its
+//! modality, and names the deciding entry in [`Consent::ledger_id`].
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ri1_core::constraints::{Consent, ConsentPolicy, FieldContext};
+use serde::{Deserialize, Serialize};
+
+use super::{Pattern, PRIMUS_CONSENT_REF};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LedgerAction {
+    Grant,
+    Revoke,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub id: String,
+    pub action: LedgerAction,
+    pub subject: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    /// Unix seconds after which a grant no longer applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// Grant ended by a revoke entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revokes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub recorded_at: u64,
+}
+
+/// State of a grant at some moment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrantStatus {
+    Active,
+    Expired,
+    RevokedBy(String),
+}
+
+impl fmt::Display for GrantStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrantStatus::Active => write!(f, "active"),
+            GrantStatus::Expired => write!(f, "expired"),
+            GrantStatus::RevokedBy(id) => write!(f, "revoked by {}", id),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LedgerError {
+    Io(std::io::Error),
+    /// Line number (1-based) and the parse error.
+    Parse(usize, serde_json::Error),
+    UnknownGrant(String),
+    AlreadyRevoked {
+        grant: String,
+        by: String,
+    },
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Io(e) => write!(f, "cannot access consent ledger: {}", e),
+            LedgerError::Parse(line, e) => {
+                write!(f, "invalid consent ledger entry on line {}: {}", line, e)
+            }
+            LedgerError::UnknownGrant(id) => write!(f, "no consent grant with id {}", id),
+            LedgerError::AlreadyRevoked { grant, by } => {
+                write!(f, "consent grant {} is already revoked by {}", grant, by)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+pub fn now_unix_s() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsentLedger {
+    path: PathBuf,
+    entries: Vec<LedgerEntry>,
+}
+
+impl ConsentLedger {
+    /// Reads the ledger at `path`; a missing file is an empty ledger.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, LedgerError> {
+        let path = path.as_ref().to_path_buf();
+        let src = match std::fs::read_to_string(&path) {
+            Ok(src) => src,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(LedgerError::Io(e)),
+        };
+        Ok(Self { entries: parse(&src)?, path })
+    }
+
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    pub fn grant(
+        &mut self,
+        subject: &str,
+        scopes: Vec<String>,
+        expires_at: Option<u64>,
+        reason: Option<String>,
+    ) -> Result<&LedgerEntry, LedgerError> {
+        self.append(|_| {
+            Ok(entry(LedgerAction::Grant, subject.into(), scopes, expires_at, None, reason))
+        })
+    }
+
+    pub fn revoke(
+        &mut self,
+        grant_id: &str,
+        reason: Option<String>,
+    ) -> Result<&LedgerEntry, LedgerError> {
+        self.append(|ledger| {
+            let grant = ledger
+                .entries
+                .iter()
+                .find(|e| e.action == LedgerAction::Grant && e.id == grant_id)
+                .ok_or_else(|| LedgerError::UnknownGrant(grant_id.into()))?;
+            if let Some(by) = ledger.revoked_by(grant_id) {
+                return Err(LedgerError::AlreadyRevoked { grant: grant_id.into(), by: by.into() });
+            }
+            Ok(entry(
+                LedgerAction::Revoke,
+                grant.subject.clone(),
+                grant.scopes.clone(),
+                None,
+                Some(grant_id.into()),
+                reason,
+            ))
+        })
+    }
+
+    /// Appends the entry `make` builds, numbered after the last one. The file is locked and
+    /// read again first, so entries other writers appended since `open` are seen and no id is
+    /// handed out twice.
+    fn append(
+        &mut self,
+        make: impl FnOnce(&Self) -> Result<LedgerEntry, LedgerError>,
+    ) -> Result<&LedgerEntry, LedgerError> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(LedgerError::Io)?;
+        file.lock().map_err(LedgerError::Io)?;
+        let mut src = String::new();
+        file.read_to_string(&mut src).map_err(LedgerError::Io)?;
+        self.entries = parse(&src)?;
+        let entry = LedgerEntry { id: format!("C{:04}", self.entries.len() + 1), ..make(self)? };
+        let line = serde_json::to_string(&entry).expect("ledger entries serialize");
+        writeln!(file, "{}", line).map_err(LedgerError::Io)?;
+        self.entries.push(entry);
+        Ok(self.entries.last().expect("just pushed"))
+    }
+
+    fn revoked_by(&self, grant_id: &str) -> Option<&str> {
+        self.entries.iter().find(|e| e.revokes.as_deref() == Some(grant_id)).map(|e| e.id.as_str())
+    }
+
+    pub fn status(&self, grant: &LedgerEntry, now: u64) -> GrantStatus {
+        match self.revoked_by(&grant.id) {
+            Some(by) => GrantStatus::RevokedBy(by.into()),
+            None if grant.expires_at.is_some_and(|t| t <= now) => GrantStatus::Expired,
+            None => GrantStatus::Active,
+        }
+    }
+
+    /// The decision for `ctx` at `now`: the latest live grant covering it, or else a denial
+    /// naming the latest grant that would have covered it.
+    pub fn evaluate_at(&self, ctx: &FieldContext, now: u64) -> Consent {
+        let subject = ctx.subject.as_deref().unwrap_or("default");
+        let covering: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.action == LedgerAction::Grant && e.subject == subject)
+            .filter(|e| {
+                e.scopes.is_empty()
+                    || Pattern::AnyOf(e.scopes.clone()).matches(ctx.modality.as_deref())
+            })
+            .collect();
+        let decide = |granted: bool, reason: String, ledger_id: Option<String>| Consent {
+            granted,
+            subject: Some(subject.into()),
+            reason: Some(reason),
+            section_ref: Some(PRIMUS_CONSENT_REF.into()),
+            ledger_id,
+        };
+        if let Some(live) =
+            covering.iter().rev().find(|g| self.status(g, now) == GrantStatus::Active)
+        {
+            return decide(true, format!("ledger grant {}", live.id), Some(live.id.clone()));
+        }
+        match covering.last() {
+            Some(g) => decide(
+                false,
+                format!("ledger grant {} {}", g.id, self.status(g, now)),
+                Some(g.id.clone()),
+            ),
+            None => decide(false, format!("no ledger grant for subject {}", subject), None),
+        }
+    }
+}
+
+/// An entry without its id, which [`ConsentLedger::append`] assigns.
+fn entry(
+    action: LedgerAction,
+    subject: String,
+    scopes: Vec<String>,
+    expires_at: Option<u64>,
+    revokes: Option<String>,
+    reason: Option<String>,
+) -> LedgerEntry {
+    LedgerEntry {
+        id: String::new(),
+        action,
+        subject,
+        scopes,
+        expires_at,
+        revokes,
+        reason,
+        recorded_at: now_unix_s(),
+    }
+}
+
+fn parse(src: &str) -> Result<Vec<LedgerEntry>, LedgerError> {
+    src.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| serde_json::from_str(l).map_err(|e| LedgerError::Parse(i + 1, e)))
+        .collect()
+}
+
+impl ConsentPolicy for ConsentLedger {
+    fn evaluate(&self, ctx: &FieldContext) -> Consent {
+        self.evaluate_at(ctx, now_unix_s())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(subject: &str, modality: &str) -> FieldContext {
+        FieldContext {
+            subject: Some(subject.into()),
+            modality: Some(modality.into()),
+            ..FieldContext::default()
+        }
+    }
+
+    #[test]
+    fn grants_revokes_and_expiry_decide_in_order() {
+        let path = std::env::temp_dir().join(format!("ri1-ledger-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut ledger = ConsentLedger::open(&path).unwrap();
+        let now = now_unix_s();
+        ledger.grant("alice", vec!["text".into()], None, None).unwrap();
+        ledger.grant("bob", Vec::new(), Some(now - 1), None).unwrap();
+        assert_eq!(
+            ledger.evaluate_at(&ctx("alice", "text"), now).ledger_id.as_deref(),
+            Some("C0001")
+        );
+        assert!(!ledger.evaluate_at(&ctx("alice", "image"), now).granted);
+        let bob = ledger.evaluate_at(&ctx("bob", "text"), now);
+        assert_eq!(
+            (bob.granted, bob.reason.as_deref()),
+            (false, Some("ledger grant C0002 expired"))
+        );
+
+        assert_eq!(ledger.revoke("C0001", None).unwrap().id, "C0003");
+        assert!(matches!(ledger.revoke("C0001", None), Err(LedgerError::AlreadyRevoked { .. })));
+        assert!(matches!(ledger.revoke("C0003", None), Err(LedgerError::UnknownGrant(_))));
+        // Reopening replays the file.
+        let reopened = ConsentLedger::open(&path).unwrap();
+        assert_eq!(reopened.entries(), ledger.entries());
+        let alice = reopened.evaluate_at(&ctx("alice", "text"), now);
+        assert_eq!(alice.reason.as_deref(), Some("ledger grant C0001 revoked by C0003"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn writers_sharing_a_file_never_reuse_ids() {
+        let path =
+            std::env::temp_dir().join(format!("ri1-ledger-shared-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut a = ConsentLedger::open(&path).unwrap();
+        let mut b = ConsentLedger::open(&path).unwrap();
+        assert_eq!(a.grant("alice", Vec::new(), None, None).unwrap().id, "C0001");
+        assert_eq!(b.grant("bob", Vec::new(), None, None).unwrap().id, "C0002");
+        // b's revoke sees a's grant, and a catches up on its next write.
+        assert_eq!(b.revoke("C0001", None).unwrap().id, "C0003");
+        assert!(matches!(a.revoke("C0001", None), Err(LedgerError::AlreadyRevoked { .. })));
+        assert_eq!(a.entries().len(), 3);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
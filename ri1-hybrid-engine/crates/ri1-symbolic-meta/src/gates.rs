@@ -0,0 +1,858 @@
+//! Operator gates and the ordered [`GateRegistry`] the engine runs them from.
+//!
+//! Each gate is bound to the `OperatorClass` and spec section its events report, as listed in
+//! the [`catalog`](crate::catalog). The engine walks the registry in order and applies each
+//! gate to the [`GateContext`] of every occurrence of its glyphs. A binding's [`GateCheck`]s
+//! turn each outcome into a `ConstraintResult` at that occurrence, so a failed `Hard` check
+//! anywhere blocks generation.
+
+use std::sync::{Arc, OnceLock};
+
+use ri1_core::constraints::{
+    ConstraintResult, GateContext, GateOutcome, OperatorClass, OperatorGate, Severity, SourceSpan,
+};
+
+use crate::catalog::{Catalog, CatalogError};
+
+/// The [`GateOutcome`] flag a [`GateCheck`] reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutcomeFlag {
+    Stabilized,
+    PreventedFusion,
+    PreventedDisruption,
+}
+
+impl OutcomeFlag {
+    pub fn read(self, out: &GateOutcome) -> bool {
+        match self {
+            OutcomeFlag::Stabilized => out.stabilized,
+            OutcomeFlag::PreventedFusion => out.prevented_fusion,
+            OutcomeFlag::PreventedDisruption => out.prevented_disruption,
+        }
+    }
+}
+
+/// Passes when `flag` of the gate's outcome equals `expected`. `severity` applies unless a
+/// `ValidatorConfig` overrides it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GateCheck {
+    pub name: &'static str,
+    pub flag: OutcomeFlag,
+    pub expected: bool,
+    pub severity: Severity,
+    /// Reported when the check fails.
+    pub message: &'static str,
+}
+
+impl GateCheck {
+    pub fn evaluate(
+        &self,
+        out: &GateOutcome,
+        severity: Severity,
+        span: Option<SourceSpan>,
+    ) -> ConstraintResult {
+        let passed = self.flag.read(out) == self.expected;
+        ConstraintResult {
+            passed,
+            severity,
+            name: self.name,
+            message: (!passed).then(|| self.message.into()),
+            span,
+        }
+    }
+}
+
+pub const DELTA_PHI_BOUNDARY: GateCheck = GateCheck {
+    name: "delta_phi_boundary",
+    flag: OutcomeFlag::PreventedFusion,
+    expected: false,
+    severity: Severity::Hard,
+    message: "Δ collapses across a Φ boundary; Φ holds the operands apart",
+};
+pub const LAMBDA_PRECURSOR: GateCheck = GateCheck {
+    name: "lambda_precursor",
+    flag: OutcomeFlag::Stabilized,
+    expected: true,
+    severity: Severity::Soft,
+    message: "Λ has no Δ/Ψ precursor to illuminate",
+};
+pub const GAMMA_PRECURSOR: GateCheck = GateCheck {
+    name: "gamma_precursor",
+    flag: OutcomeFlag::Stabilized,
+    expected: true,
+    severity: Severity::Soft,
+    message: "Γ has no Δ/Λ precursor to carry forward",
+};
+pub const EPSILON_CONTEXT: GateCheck = GateCheck {
+    name: "epsilon_context",
+    flag: OutcomeFlag::Stabilized,
+    expected: true,
+    severity: Severity::Soft,
+    message: "ε has no ongoing recursion to activate",
+};
+pub const GROWTH_LOOP: GateCheck = GateCheck {
+    name: "growth_loop",
+    flag: OutcomeFlag::Stabilized,
+    expected: true,
+    severity: Severity::Soft,
+    message: "Γ̇ has no enclosing loop to recurse through",
+};
+pub const PI_SUPPORT: GateCheck = GateCheck {
+    name: "pi_support",
+    flag: OutcomeFlag::PreventedDisruption,
+    expected: true,
+    severity: Severity::Soft,
+    message: "Π extends without Φ/Σ support",
+};
+pub const WILL_FORCE_CLOSURE: GateCheck = GateCheck {
+    name: "will_force_closure",
+    flag: OutcomeFlag::PreventedDisruption,
+    expected: true,
+    severity: Severity::Soft,
+    message: "ω cannot persist into Ω closure",
+};
+
+/// A gate with the class, section and glyphs its events are reported under.
+#[derive(Clone)]
+pub struct GateBinding {
+    pub gate: Arc<dyn OperatorGate>,
+    pub class: OperatorClass,
+    pub section_ref: Option<String>,
+    /// Glyphs that trigger the gate: its own symbol first, then aliases.
+    pub glyphs: Vec<String>,
+    pub checks: Vec<GateCheck>,
+}
+
+impl GateBinding {
+    pub fn new<G: OperatorGate + 'static>(
+        gate: G,
+        class: OperatorClass,
+        section_ref: Option<&str>,
+    ) -> Self {
+        let glyphs = vec![gate.symbol().to_string()];
+        Self {
+            gate: Arc::new(gate),
+            class,
+            section_ref: section_ref.map(String::from),
+            glyphs,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Another glyph that triggers the gate, e.g. `ρ` for `Ρ`.
+    pub fn alias(mut self, glyph: &str) -> Self {
+        self.glyphs.push(glyph.to_string());
+        self
+    }
+
+    /// Judges every application of the gate with `check`.
+    pub fn check(mut self, check: GateCheck) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        self.gate.symbol()
+    }
+}
+
+/// Gates keyed by symbol, applied in registration order.
+#[derive(Clone, Default)]
+pub struct GateRegistry {
+    bindings: Vec<GateBinding>,
+}
+
+impl GateRegistry {
+    /// A binding per gated entry of the built-in catalog, in catalog order.
+    pub fn builtin() -> Self {
+        Self::from_catalog(&Catalog::builtin()).expect("built-in catalog names only built-in gates")
+    }
+
+    /// Binds the built-in gate of every gated catalog operator under the catalog's class,
+    /// section and glyphs.
+    pub fn from_catalog(catalog: &Catalog) -> Result<Self, CatalogError> {
+        let mut r = Self::default();
+        for o in catalog.operators.iter().filter(|o| o.gated) {
+            let gate =
+                builtin_gate(&o.symbol).ok_or_else(|| CatalogError::NoGate(o.symbol.clone()))?;
+            r.register(GateBinding {
+                gate,
+                class: o.class,
+                section_ref: o.section_ref.clone(),
+                glyphs: o.glyphs().map(String::from).collect(),
+                checks: builtin_checks(&o.symbol).to_vec(),
+            });
+        }
+        Ok(r)
+    }
+
+    /// Shared copy of [`GateRegistry::builtin`].
+    pub(crate) fn builtin_ref() -> &'static GateRegistry {
+        static BUILTIN: OnceLock<GateRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(GateRegistry::builtin)
+    }
+
+    /// Replaces the gate bound to the same symbol in place, keeping its position; otherwise
+    /// appends.
+    pub fn register(&mut self, binding: GateBinding) {
+        match self.bindings.iter().position(|b| b.symbol() == binding.symbol()) {
+            Some(i) => self.bindings[i] = binding,
+            None => self.bindings.push(binding),
+        }
+    }
+
+    /// Registers `binding` so it runs immediately before the gate for `before`; appends if
+    /// there is none. A gate already bound to the same symbol is removed first.
+    pub fn register_before(&mut self, before: &str, binding: GateBinding) {
+        self.remove(binding.symbol());
+        let at =
+            self.bindings.iter().position(|b| b.symbol() == before).unwrap_or(self.bindings.len());
+        self.bindings.insert(at, binding);
+    }
+
+    pub fn remove(&mut self, symbol: &str) -> Option<GateBinding> {
+        let i = self.bindings.iter().position(|b| b.symbol() == symbol)?;
+        Some(self.bindings.remove(i))
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&GateBinding> {
+        self.bindings.iter().find(|b| b.symbol() == symbol)
+    }
+
+    /// The gate triggered by `glyph`, through its symbol or an alias.
+    pub fn gate_for(&self, glyph: &str) -> Option<&dyn OperatorGate> {
+        self.bindings.iter().find(|b| b.glyphs.iter().any(|g| g == glyph)).map(|b| b.gate.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GateBinding> {
+        self.bindings.iter()
+    }
+
+    pub fn symbols(&self) -> Vec<&'static str> {
+        self.bindings.iter().map(|b| b.symbol()).collect()
+    }
+
+    /// Checks of every bound gate, in gate order.
+    pub fn checks(&self) -> impl Iterator<Item = &GateCheck> {
+        self.bindings.iter().flat_map(|b| &b.checks)
+    }
+}
+
+/// The built-in gate implementing a catalog symbol.
+pub(crate) fn builtin_gate(symbol: &str) -> Option<Arc<dyn OperatorGate>> {
+    let gate: Arc<dyn OperatorGate> = match symbol {
+        "χ" => Arc::new(ChiGate),
+        "Ε" => Arc::new(EpsilonCapitalGate),
+        "Φ" => Arc::new(PhiGate),
+        "Ω" => Arc::new(OmegaGate),
+        "Ψ" => Arc::new(PsiGate),
+        "Ρ" => Arc::new(RhoGate),
+        "Τ" => Arc::new(TauGate),
+        "Θ" => Arc::new(ThetaGate),
+        "ε" => Arc::new(EpsilonGate),
+        "ω" => Arc::new(OmegaLowerGate),
+        "δ" => Arc::new(DeltaLowerGate),
+        "Δ" => Arc::new(DeltaGate),
+        "Λ" => Arc::new(LambdaGate),
+        "Γ" => Arc::new(GammaGate),
+        "Γ̇" => Arc::new(GammaDotGate),
+        "Ξ" => Arc::new(XiGate),
+        "ζ" => Arc::new(ZetaGate),
+        "λ" => Arc::new(LambdaLowerGate),
+        "Σ" => Arc::new(SigmaGate),
+        "Π" => Arc::new(PiGate),
+        _ => return None,
+    };
+    Some(gate)
+}
+
+fn builtin_checks(symbol: &str) -> &'static [GateCheck] {
+    match symbol {
+        "ε" => &[EPSILON_CONTEXT],
+        "ω" => &[WILL_FORCE_CLOSURE],
+        "Δ" => &[DELTA_PHI_BOUNDARY],
+        "Λ" => &[LAMBDA_PRECURSOR],
+        "Γ" => &[GAMMA_PRECURSOR],
+        "Γ̇" => &[GROWTH_LOOP],
+        "Π" => &[PI_SUPPORT],
+        _ => &[],
+    }
+}
+
+// χ — Measurement → Perception Bridge (Section 020)
+struct ChiGate;
+
+impl OperatorGate for ChiGate {
+    fn symbol(&self) -> &'static str {
+        "χ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Observation that runs into Ω is absorbed by the closure instead of bridging onward.
+        let into_closure = cx.followed_by(&["Ω"]);
+        GateOutcome {
+            stabilized: true,       // tunes field harmonically, collapses to perceivable state
+            prevented_fusion: true, // measurement bridge is non-fusional
+            prevented_disruption: true, // non-destructive by default
+            note: Some(if into_closure {
+                "χ with Ω: observation collapses into terminal integration".into()
+            } else {
+                "χ measurement→perception: harmonic tuning; observer bridge".into()
+            }),
+        }
+    }
+}
+
+// Ε — Ignition / Initiation (Section 019)
+struct EpsilonCapitalGate;
+
+impl OperatorGate for EpsilonCapitalGate {
+    fn symbol(&self) -> &'static str {
+        "Ε"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Ignition starts a field from rest, or re-opens one Ω closed; a field already in
+        // motion is jolted rather than started.
+        let reignites = cx.preceding.first().is_some_and(|p| p == "Ω");
+        let in_motion = !cx.preceding.is_empty() && !reignites;
+        let collapses = cx.following.first().is_some_and(|f| f == "Δ");
+        GateOutcome {
+            stabilized: !collapses, // starts motion from rest without immediate collapse
+            prevented_fusion: true, // context-free: ignition is never fusion
+            prevented_disruption: !in_motion, // initiation should not disrupt cohesion
+            note: Some(if reignites {
+                "Ε re-ignites a field closed by Ω; phase initiation".into()
+            } else if in_motion {
+                "Ε ignites a field already in motion; initiation jolts its cohesion".into()
+            } else {
+                "Ε ignition: spark of kinetic chain reaction; phase initiation".into()
+            }),
+        }
+    }
+}
+
+// Τ — Synchronicity / Readiness (Section 018)
+struct TauGate;
+
+impl OperatorGate for TauGate {
+    fn symbol(&self) -> &'static str {
+        "Τ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Readiness is taken up by a transformation later in the thread.
+        let ready = cx.followed_by(&["Δ", "δ", "Γ", "Γ̇", "Ξ", "Π"]);
+        GateOutcome {
+            stabilized: ready,          // enables transformation readiness without forcing it
+            prevented_fusion: true,     // context-free: not a synthesis itself
+            prevented_disruption: true, // context-free: coherence via alignment
+            note: Some(if ready {
+                "Τ marks recursive readiness via synchronistic alignment".into()
+            } else {
+                "Τ readiness with no transformation after it; alignment idles".into()
+            }),
+        }
+    }
+}
+
+// Θ — Intention Vector (Section 016)
+struct ThetaGate;
+
+impl OperatorGate for ThetaGate {
+    fn symbol(&self) -> &'static str {
+        "Θ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Intention orients what comes after it in the thread.
+        let directed = !cx.following.is_empty();
+        GateOutcome {
+            stabilized: directed,   // configures field orientation without forcing action
+            prevented_fusion: true, // context-free: intention is non-fusional
+            prevented_disruption: true, // context-free: orientation does not disrupt coherence
+            note: Some(if directed {
+                "Θ sets directed recursive potential (field intention)".into()
+            } else {
+                "Θ with nothing after it; the intention vector has no target".into()
+            }),
+        }
+    }
+}
+
+// δ — Micro-Transformation (Section 015)
+struct DeltaLowerGate;
+
+impl OperatorGate for DeltaLowerGate {
+    fn symbol(&self) -> &'static str {
+        "δ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // A mutation right beside a Δ is swept into its fusion.
+        let beside_fusion =
+            [cx.preceding.first(), cx.following.first()].into_iter().flatten().any(|g| g == "Δ");
+        GateOutcome {
+            stabilized: true, // context-free: low-intensity evolution maintains coherence
+            prevented_fusion: !beside_fusion, // a small mutation, not a fuse
+            prevented_disruption: !beside_fusion, // designed to avoid disruption
+            note: Some(if beside_fusion {
+                "δ beside Δ: micro-transformation is absorbed into the fusion".into()
+            } else {
+                "δ applies subtle recursion mutation / fine-grain adjustment".into()
+            }),
+        }
+    }
+}
+
+// Ρ — Perceptual Modulation (Section 014)
+struct RhoGate;
+
+impl OperatorGate for RhoGate {
+    fn symbol(&self) -> &'static str {
+        "Ρ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // A lens needs an operand in its thread to refract.
+        let framed = !cx.preceding.is_empty() || !cx.following.is_empty();
+        GateOutcome {
+            stabilized: framed, // alters meaning trajectory via frame without breaking identity
+            prevented_fusion: true, // context-free: modulation is not synthesis
+            prevented_disruption: true, // context-free: bends interpretation, keeps coherence
+            note: Some(if framed {
+                "Ρ/ρ applies perceptual lens: refraction of recursion under context".into()
+            } else {
+                "Ρ/ρ without an operand to refract; perceptual lens idles".into()
+            }),
+        }
+    }
+}
+
+// ω — Will-Force (Section 013)
+struct OmegaLowerGate;
+
+impl OperatorGate for OmegaLowerGate {
+    fn symbol(&self) -> &'static str {
+        "ω"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        let into_closure = cx.followed_by(&["Ω"]);
+        GateOutcome {
+            stabilized: true, // introduces bias without breaking identity
+            prevented_fusion: false,
+            prevented_disruption: !into_closure, // will-force does not survive closure
+            note: Some(if into_closure {
+                "ω present with Ω; will-force cannot persist into closure".into()
+            } else {
+                "ω encodes autogenic intention vector within recursion".into()
+            }),
+        }
+    }
+}
+
+// λ — Entanglement (Section 012)
+struct LambdaLowerGate;
+
+impl OperatorGate for LambdaLowerGate {
+    fn symbol(&self) -> &'static str {
+        "λ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Entanglement binds the operands beside it (or its arguments, which follow it); a
+        // later Δ collapses every thread it binds.
+        let bound = !cx.preceding.is_empty() || !cx.following.is_empty();
+        let collapses = cx.followed_by(&["Δ"]);
+        GateOutcome {
+            stabilized: bound,                // binds threads without forcing synthesis
+            prevented_fusion: true,           // context-free: explicitly non-fusional
+            prevented_disruption: !collapses, // linkage preserves coherence under change
+            note: Some(if !bound {
+                "λ without threads to link; entanglement has nothing to bind".into()
+            } else if collapses {
+                "λ links threads that a later Δ collapses together".into()
+            } else {
+                "λ links recursion threads via non-local dependency".into()
+            }),
+        }
+    }
+}
+
+// ζ — Recurrence Pattern (Section 011)
+struct ZetaGate;
+
+impl OperatorGate for ZetaGate {
+    fn symbol(&self) -> &'static str {
+        "ζ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Recurrence needs a loop, or a motif that returns on both sides of ζ.
+        let recurring = cx.loop_depth > 0 || cx.preceding.iter().any(|p| cx.following.contains(p));
+        GateOutcome {
+            stabilized: recurring,      // marks patterned return without altering identity
+            prevented_fusion: true,     // context-free: does not enforce synthesis
+            prevented_disruption: true, // context-free: protects recurrent motif coherence
+            note: Some(if recurring {
+                "ζ signals meaningful recurrence across recursion intervals".into()
+            } else {
+                "ζ without a loop or returning motif; no recurrence to mark".into()
+            }),
+        }
+    }
+}
+
+// ε — Micro-Ignition (Section 010)
+struct EpsilonGate;
+
+impl OperatorGate for EpsilonGate {
+    fn symbol(&self) -> &'static str {
+        "ε"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Micro-ignition needs an ongoing recursion: a loop around it or operands before it.
+        let ongoing = cx.loop_depth > 0 || !cx.preceding.is_empty();
+        GateOutcome {
+            stabilized: ongoing,
+            prevented_fusion: true, // acts as precise activation, not collapse
+            prevented_disruption: true,
+            note: Some(if ongoing {
+                "ε triggers a local branch/activation within an ongoing recursion".into()
+            } else {
+                "ε encountered without surrounding context; micro-activation ignored".into()
+            }),
+        }
+    }
+}
+
+// Π — Transcendent Continuity (Section 009)
+struct PiGate;
+
+impl OperatorGate for PiGate {
+    fn symbol(&self) -> &'static str {
+        "Π"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        let supported = cx.preceded_by(&["Φ", "Σ"]) || cx.followed_by(&["Φ", "Σ"]);
+        GateOutcome {
+            stabilized: true, // sustained coherence while scaling
+            prevented_fusion: false,
+            prevented_disruption: supported, // requires Φ/Σ support to avoid entropy
+            note: Some(if supported {
+                "Π maintains pattern fidelity under infinite extension".into()
+            } else {
+                "Π without Φ/Σ support: extension risks entropy".into()
+            }),
+        }
+    }
+}
+
+// Ψ — Oscillation (Section 008)
+struct PsiGate;
+
+impl OperatorGate for PsiGate {
+    fn symbol(&self) -> &'static str {
+        "Ψ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Inside a loop, oscillation compounds each iteration unless Φ damps it.
+        let undamped = cx.loop_depth > 0 && !cx.preceded_by(&["Φ"]) && !cx.followed_by(&["Φ"]);
+        GateOutcome {
+            stabilized: !undamped,   // modulates without breaking identity
+            prevented_fusion: false, // context-free: oscillation may feed a fusion
+            prevented_disruption: !undamped,
+            note: Some(if undamped {
+                "Ψ oscillates inside a loop without Φ damping; amplitude compounds".into()
+            } else {
+                "Ψ oscillation: modulation of recursive tempo/pressure".into()
+            }),
+        }
+    }
+}
+
+// Γ — Directional Growth (Section 007)
+struct GammaGate;
+
+impl OperatorGate for GammaGate {
+    fn symbol(&self) -> &'static str {
+        "Γ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Growth carries forward what an earlier Δ or Λ in the same thread produced.
+        let precursor = cx.preceded_by(&["Δ", "Λ"]);
+        GateOutcome {
+            stabilized: precursor,      // preserves form through transformation
+            prevented_fusion: false,    // allows prior Δ output to persist
+            prevented_disruption: true, // growth under continuity resists disruption
+            note: Some(if precursor {
+                "Γ carries recursive identity forward with structured evolution".into()
+            } else {
+                "Γ encountered without Δ/Λ precursor; marking as directional modulation".into()
+            }),
+        }
+    }
+}
+
+// Γ̇ — Recursive Growth (Section 007)
+struct GammaDotGate;
+
+impl OperatorGate for GammaDotGate {
+    fn symbol(&self) -> &'static str {
+        "Γ̇"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Recursive growth feeds its output back, so it needs a loop to return through.
+        let looped = cx.loop_depth > 0;
+        GateOutcome {
+            stabilized: looped,
+            prevented_fusion: false,
+            prevented_disruption: true,
+            note: Some(if looped {
+                "Γ̇ compounds growth across loop iterations".into()
+            } else {
+                "Γ̇ encountered outside a loop; growth applies once".into()
+            }),
+        }
+    }
+}
+
+// Ξ — Emergent System (Section 006)
+struct XiGate;
+
+impl OperatorGate for XiGate {
+    fn symbol(&self) -> &'static str {
+        "Ξ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Emergence needs layered recursion: a loop, or at least two operands before it.
+        let layered = cx.loop_depth > 0 || cx.preceding.len() >= 2;
+        GateOutcome {
+            stabilized: layered,        // emergence yields coherent systemic behavior
+            prevented_fusion: false,    // context-free: may include fused parts
+            prevented_disruption: true, // context-free: systemic coherence resists disruption
+            note: Some(if layered {
+                "Ξ encodes emergent systemic coherence from layered recursion".into()
+            } else {
+                "Ξ without layered recursion beneath it; emergence is nominal".into()
+            }),
+        }
+    }
+}
+
+// Σ — Coexistence / Plurality (Section 005)
+struct SigmaGate;
+
+impl OperatorGate for SigmaGate {
+    fn symbol(&self) -> &'static str {
+        "Σ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Coexistence contains at least two fields: its arguments or the operands beside it.
+        let plural = cx.preceding.len() + cx.following.len() >= 2;
+        GateOutcome {
+            stabilized: plural,         // structural containment for active unresolved fields
+            prevented_fusion: true,     // context-free: no enforced synthesis
+            prevented_disruption: true, // context-free: coexistence without collapse
+            note: Some(if plural {
+                "Σ sustains parallel recursion without enforced synthesis or collapse".into()
+            } else {
+                "Σ with a single field; nothing coexists".into()
+            }),
+        }
+    }
+}
+
+// Ω — Closure / Integration (Section 004)
+struct OmegaGate;
+
+impl OperatorGate for OmegaGate {
+    fn symbol(&self) -> &'static str {
+        "Ω"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Closure integrates what precedes it; inside a loop it cuts the recursion short.
+        let closes = !cx.preceding.is_empty();
+        let in_loop = cx.loop_depth > 0;
+        GateOutcome {
+            stabilized: closes,
+            prevented_fusion: true, // context-free: closure integrates, it does not fuse
+            prevented_disruption: !in_loop,
+            note: Some(if in_loop {
+                "Ω inside a loop container closes the recursion it holds".into()
+            } else if closes {
+                "Ω closes active recursion and fixes structure".into()
+            } else {
+                "Ω with nothing before it; no active recursion to close".into()
+            }),
+        }
+    }
+}
+
+// Φ — Harmonic Stabilization (Section 001)
+struct PhiGate;
+
+impl OperatorGate for PhiGate {
+    fn symbol(&self) -> &'static str {
+        "Φ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Φ holds tension between operands in its thread; alone it holds nothing.
+        let holds = !cx.preceding.is_empty() || !cx.following.is_empty();
+        GateOutcome {
+            stabilized: holds,
+            prevented_fusion: true, // context-free: Φ is non-fusional by definition
+            prevented_disruption: true, // context-free: non-neutralizing, recursion-safe
+            note: Some(if holds {
+                "Φ preserves tension: non-fusional, non-neutralizing, recursion-safe".into()
+            } else {
+                "Φ with no operands to hold in tension".into()
+            }),
+        }
+    }
+}
+
+// Δ — Fusion Transformation (Section 002)
+struct DeltaGate;
+
+impl OperatorGate for DeltaGate {
+    fn symbol(&self) -> &'static str {
+        "Δ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // A Φ beside Δ holds the operands apart, so nothing fuses; otherwise fusion collapses
+        // the field for good.
+        let fuses = !cx.phi_boundary;
+        GateOutcome {
+            stabilized: false,
+            prevented_fusion: !fuses,
+            prevented_disruption: !fuses, // the collapse is irreversible
+            note: Some(if fuses {
+                "Δ fusion collapse enacted".into()
+            } else {
+                "Δ fusion prevented across Φ boundary".into()
+            }),
+        }
+    }
+}
+
+// Λ — Structural Illumination (Section 003)
+struct LambdaGate;
+
+impl OperatorGate for LambdaGate {
+    fn symbol(&self) -> &'static str {
+        "Λ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        // Illumination needs a Δ or Ψ thread before it to render.
+        let precursor = cx.preceded_by(&["Δ", "Ψ"]);
+        GateOutcome {
+            stabilized: precursor, // renders coherent structure
+            prevented_fusion: false,
+            prevented_disruption: precursor, // illumination resists disruption post-resolution
+            note: Some(if precursor {
+                "Λ renders structural clarity from recursive fields".into()
+            } else {
+                "Λ encountered without precursor Δ/Ψ; marking as illumination attempt".into()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StrictOmega;
+
+    impl OperatorGate for StrictOmega {
+        fn symbol(&self) -> &'static str {
+            "Ω"
+        }
+
+        fn apply(&self, _cx: &GateContext<'_>) -> GateOutcome {
+            GateOutcome {
+                stabilized: false,
+                prevented_fusion: true,
+                prevented_disruption: false,
+                note: Some("strict Ω".into()),
+            }
+        }
+    }
+
+    #[test]
+    fn overrides_keep_position() {
+        let mut r = GateRegistry::builtin();
+        let before = r.symbols();
+        r.register(GateBinding::new(StrictOmega, OperatorClass::ClosureIntegration, Some("004")));
+        assert_eq!(r.symbols(), before);
+        let note = r.get("Ω").unwrap().gate.apply(&GateContext::new("Ω")).note;
+        assert_eq!(note.as_deref(), Some("strict Ω"));
+    }
+
+    /// Outcome of the built-in gate for the first `glyph` in `src`.
+    fn outcome(src: &str, glyph: &str) -> GateOutcome {
+        let ast = crate::interaction::parse::parse(src);
+        let contexts = crate::interaction::neighborhood::gate_contexts(&ast, src);
+        let (_, cx) = contexts.iter().find(|(s, _)| s.is(glyph)).expect("occurrence");
+        GateRegistry::builtin().gate_for(glyph).expect("gate").apply(cx)
+    }
+
+    #[test]
+    fn outcomes_follow_the_neighbourhood() {
+        assert!(outcome("Ψ Ω", "Ω").stabilized && !outcome("Ω", "Ω").stabilized);
+        assert!(!outcome("[Ψ Ω]", "Ω").prevented_disruption);
+        assert!(
+            outcome("Ε Ψ", "Ε").prevented_disruption && !outcome("Ψ Ε Γ", "Ε").prevented_disruption
+        );
+        assert!(outcome("Ψ Ω Ε Γ", "Ε").prevented_disruption);
+        assert!(outcome("Τ → Δ", "Τ").stabilized && !outcome("Δ → Τ", "Τ").stabilized);
+        assert!(!outcome("δ Δ", "δ").prevented_fusion && outcome("δ Ψ", "δ").prevented_fusion);
+        assert!(outcome("Σ(Ψ, Γ)", "Σ").stabilized && !outcome("Σ", "Σ").stabilized);
+        assert!(
+            outcome("[ζ Ψ]", "ζ").stabilized
+                && outcome("Ψ ζ Ψ", "ζ").stabilized
+                && !outcome("Ψ ζ Γ", "ζ").stabilized
+        );
+        assert!(!outcome("[Ψ Γ]", "Ψ").stabilized && outcome("[Φ Ψ]", "Ψ").stabilized);
+        assert!(outcome("Ψ Γ Ξ", "Ξ").stabilized && !outcome("Ψ Ξ", "Ξ").stabilized);
+        assert!(
+            outcome("λ(Ψ, Γ)", "λ").prevented_disruption
+                && !outcome("Ψ λ Γ → Δ", "λ").prevented_disruption
+        );
+        assert!(
+            !outcome("Θ", "Θ").stabilized
+                && outcome("Ρ Ψ", "Ρ").stabilized
+                && outcome("Ψ : Φ", "Φ").stabilized
+        );
+        assert!(
+            !outcome("Δ Ψ", "Δ").prevented_disruption && outcome("Δ Φ", "Δ").prevented_disruption
+        );
+    }
+
+    #[test]
+    fn explicit_ordering_and_aliases() {
+        let mut r = GateRegistry::builtin();
+        r.register_before(
+            "χ",
+            GateBinding::new(StrictOmega, OperatorClass::ClosureIntegration, None),
+        );
+        assert_eq!(&r.symbols()[..2], ["Ω", "χ"]);
+        assert_eq!(r.symbols().iter().filter(|s| **s == "Ω").count(), 1);
+        assert_eq!(r.gate_for("ρ").map(|g| g.symbol()), Some("Ρ"));
+        assert_eq!(r.gate_for("τ").map(|g| g.symbol()), Some("Τ"));
+        assert!(r.gate_for("n").is_none());
+    }
+}
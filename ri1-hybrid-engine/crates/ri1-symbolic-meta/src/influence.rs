@@ -1,23 +1,8 @@
 use std::collections::{HashMap, HashSet};
 use ri1_core::constraints::{OperatorClass, ResonanceEvent};
+pub use ri1_core::constraints::{InfluenceEdge, InfluenceSnapshot, OperatorWeight};
 
-#[derive(serde::Serialize, Debug, Clone)]
-pub struct OperatorWeight { pub operator: OperatorClass, pub weight: f64 }
-
-#[derive(serde::Serialize, Debug, Clone)]
-pub struct InfluenceEdge { pub from: OperatorClass, pub to: OperatorClass, pub relation: String, pub weight: f64 }
-
-#[derive(serde::Serialize, Debug, Clone)]
-pub struct InfluenceSnapshot {
-    pub resonance_index: f64,
-    pub operator_influence: Vec<OperatorWeight>,
-    pub cooperation_count: usize,
-    pub conflict_count: usize,
-    pub negotiation: Vec<InfluenceEdge>,
-    pub notes: Option<String>,
-}
-
-pub fn compute_influence(events: &Vec<ResonanceEvent>) -> (InfluenceSnapshot, ResonanceEvent) {
+pub fn compute_influence(events: &[ResonanceEvent]) -> (InfluenceSnapshot, ResonanceEvent) {
     use OperatorClass::*;
     let mut score: HashMap<OperatorClass, f64> = HashMap::new();
     let mut has: HashSet<OperatorClass> = HashSet::new();
@@ -56,8 +41,7 @@ pub fn compute_influence(events: &Vec<ResonanceEvent>) -> (InfluenceSnapshot, Re
     if violations > 0 { r -= 0.10; }
     if conflicts > 1 { r -= 0.05 * ((conflicts - 1) as f64).min(4.0); }
     if has.contains(&LoopCycle) && has.contains(&ClosureIntegration) { r += 0.05; }
-    if r < 0.0 { r = 0.0; }
-    if r > 1.0 { r = 1.0; }
+    r = r.clamp(0.0, 1.0);
 
     let top = operator_influence.iter().take(3).map(|ow| format!("{:?}:{:.2}", ow.operator, ow.weight)).collect::<Vec<_>>().join(", ");
     let summary = ResonanceEvent {
@@ -65,6 +49,9 @@ pub fn compute_influence(events: &Vec<ResonanceEvent>) -> (InfluenceSnapshot, Re
         message: format!("influence_summary: resonance_index={:.2} top=[{}] coop={} conflict={}", r, top, coop, conflicts),
         section_ref: None,
         symbol: None,
+        code: None,
+        span: None,
+        suggestions: Vec::new(),
     };
 
     (InfluenceSnapshot { resonance_index: r, operator_influence, cooperation_count: coop, conflict_count: conflicts, negotiation, notes: None }, summary)
@@ -0,0 +1,259 @@
+//! Typed syntax tree for Phipe expressions.
+//!
+//! Every node carries a byte [`Span`] into the source string so later passes can reason
+//! about operand order, scope and adjacency instead of scanning the raw text.
+
+use ri1_core::constraints::{OperatorClass, SourceSpan};
+
+/// Half-open byte range `start..end` into the source string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Zero-width span at `at`, used for missing operands.
+    pub fn point(at: usize) -> Self {
+        Self { start: at, end: at }
+    }
+
+    /// Smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span { start: self.start.min(other.start), end: self.end.max(other.end) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Resolves this byte range against `source` into a [`SourceSpan`] with line/column.
+    pub fn locate(self, source: &str) -> SourceSpan {
+        SourceSpan::locate(source, self.start, self.end)
+    }
+}
+
+/// The seven Φπε conditionals. `LoopCycle` is expressed structurally as [`ExprKind::Loop`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Conditional {
+    Flow,
+    Simultaneity,
+    Interaction,
+    Disruption,
+    Orthogonality,
+    LoopCycle,
+    Stabilization,
+}
+
+impl Conditional {
+    pub const ALL: [Conditional; 7] = [
+        Conditional::Flow,
+        Conditional::Simultaneity,
+        Conditional::Interaction,
+        Conditional::Disruption,
+        Conditional::Orthogonality,
+        Conditional::LoopCycle,
+        Conditional::Stabilization,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Conditional::Flow => "→",
+            Conditional::Simultaneity => "+",
+            Conditional::Interaction => ":",
+            Conditional::Disruption => "/",
+            Conditional::Orthogonality => "|",
+            Conditional::LoopCycle => "[]",
+            Conditional::Stabilization => "=",
+        }
+    }
+
+    pub fn class(&self) -> OperatorClass {
+        match self {
+            Conditional::Flow => OperatorClass::FlowVector,
+            Conditional::Simultaneity => OperatorClass::Simultaneity,
+            Conditional::Interaction => OperatorClass::InteractionInterface,
+            Conditional::Disruption => OperatorClass::Disruption,
+            Conditional::Orthogonality => OperatorClass::Orthogonality,
+            Conditional::LoopCycle => OperatorClass::LoopCycle,
+            Conditional::Stabilization => OperatorClass::StabilizationResolution,
+        }
+    }
+
+    /// Binding strength for infix use; higher binds tighter. Juxtaposition binds tighter
+    /// than any conditional.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Conditional::Stabilization => 1,
+            Conditional::Flow => 2,
+            Conditional::Orthogonality => 3,
+            Conditional::Disruption => 4,
+            Conditional::Interaction => 5,
+            Conditional::Simultaneity => 6,
+            Conditional::LoopCycle => u8::MAX,
+        }
+    }
+}
+
+/// Superscript modifier attached to a symbol, e.g. the `ⁿ` in `Ψⁿ`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modifier {
+    pub glyph: char,
+    pub span: Span,
+}
+
+/// An operator symbol: base glyph, trailing combining marks and superscript modifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub base: char,
+    /// Combining marks following the base, e.g. U+0307 in `Γ̇`.
+    pub marks: String,
+    pub modifiers: Vec<Modifier>,
+    pub span: Span,
+}
+
+impl Symbol {
+    /// Base glyph plus combining marks, without modifiers (e.g. `Γ̇` for `Γ̇ⁿ`).
+    pub fn glyph(&self) -> String {
+        let mut s = String::with_capacity(self.base.len_utf8() + self.marks.len());
+        s.push(self.base);
+        s.push_str(&self.marks);
+        s
+    }
+
+    pub fn is(&self, glyph: &str) -> bool {
+        let mut chars = glyph.chars();
+        chars.next() == Some(self.base) && chars.as_str() == self.marks
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    Symbol(Symbol),
+    /// Run of non-Phipe text such as a prompt prefix (`TEXT`) or a bare index `n`.
+    Text(String),
+    /// `Σ(Ψ, Γ)`, `ζ(Ψ)`.
+    Apply {
+        head: Symbol,
+        args: Vec<Expr>,
+    },
+    /// `[ ... ]` loop/cycle container; `None` for an empty `[]`.
+    Loop(Option<Box<Expr>>),
+    /// Parenthesised sub-expression without a head symbol.
+    Group(Box<Expr>),
+    /// Juxtaposed operands, e.g. `ΕΔΦ` or `Δ Λ Ψ`.
+    Sequence(Vec<Expr>),
+    Binary {
+        op: Conditional,
+        op_span: Span,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    /// Placeholder for an operand the parser expected but did not find.
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    /// The symbol this node stands for when it is a bare symbol or an application head.
+    pub fn head_symbol(&self) -> Option<&Symbol> {
+        match &self.kind {
+            ExprKind::Symbol(s) => Some(s),
+            ExprKind::Apply { head, .. } => Some(head),
+            _ => None,
+        }
+    }
+
+    /// Direct child nodes in source order.
+    pub fn children(&self) -> Vec<&Expr> {
+        match &self.kind {
+            ExprKind::Apply { args, .. } => args.iter().collect(),
+            ExprKind::Loop(Some(body)) | ExprKind::Group(body) => vec![body.as_ref()],
+            ExprKind::Sequence(items) => items.iter().collect(),
+            ExprKind::Binary { lhs, rhs, .. } => vec![lhs.as_ref(), rhs.as_ref()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Pre-order traversal; `loop_depth` counts enclosing `[]` containers.
+    pub fn walk<'a>(&'a self, f: &mut dyn FnMut(&'a Expr, usize)) {
+        self.walk_inner(0, f);
+    }
+
+    fn walk_inner<'a>(&'a self, depth: usize, f: &mut dyn FnMut(&'a Expr, usize)) {
+        f(self, depth);
+        let depth = if matches!(self.kind, ExprKind::Loop(_)) { depth + 1 } else { depth };
+        for c in self.children() {
+            c.walk_inner(depth, f);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// An operand was expected around a conditional or inside `()`.
+    MissingOperand,
+    /// `[` or `(` without its closer.
+    Unclosed,
+    /// Closer or `,` with nothing to close.
+    Unmatched,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub message: String,
+    pub span: Span,
+}
+
+/// Result of [`parse`](super::parse::parse): a best-effort tree plus recovered errors.
+#[derive(Debug, Clone, Default)]
+pub struct Ast {
+    pub root: Option<Expr>,
+    pub errors: Vec<ParseError>,
+}
+
+impl Ast {
+    /// Every symbol occurrence (including application heads) in source order.
+    pub fn symbols(&self) -> Vec<&Symbol> {
+        let mut out = Vec::new();
+        if let Some(root) = &self.root {
+            root.walk(&mut |e, _| {
+                if let Some(s) = e.head_symbol() {
+                    out.push(s);
+                }
+            });
+        }
+        out
+    }
+
+    pub fn contains_symbol(&self, glyph: &str) -> bool {
+        self.symbols().iter().any(|s| s.is(glyph))
+    }
+
+    /// Pairs of neighbouring operands inside juxtaposed sequences, e.g. `(Δ, Φ)` for `ΔΦ`.
+    pub fn adjacent_pairs(&self) -> Vec<(&Expr, &Expr)> {
+        let mut out = Vec::new();
+        if let Some(root) = &self.root {
+            root.walk(&mut |e, _| {
+                if let ExprKind::Sequence(items) = &e.kind {
+                    out.extend(items.windows(2).map(|w| (&w[0], &w[1])));
+                }
+            });
+        }
+        out
+    }
+}
@@ -0,0 +1,294 @@
+//! Algebraic canonicalization of Phipe expressions.
+//!
+//! The parsed tree is rewritten bottom-up with the declared [`Law`]s until it reaches a
+//! canonical form; two expressions are equivalent when their canonical forms print the same.
+//! Operand order for commutative laws is the lexical order of the formatted operands.
+//! Expressions that fail to execute (e.g. `Δ` after `Ω`) have no canonical form and are not
+//! equivalent to anything.
+
+use std::fmt;
+
+use super::ast::{Conditional, Expr, ExprKind, Span};
+use super::format::render;
+use super::interpret::{execute_ast, SemanticError};
+use super::parse::parse;
+use crate::gates::GateRegistry;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Law {
+    /// `a + b ≡ b + a`
+    SimultaneityCommutes,
+    /// `Σ(a, b) ≡ Σ(b, a)`
+    CoexistenceCommutes,
+    /// `[[a]] ≡ [a]`
+    LoopIdempotent,
+    /// `(a = b) → c ≡ a = b` and `(a = b) = c ≡ a = b`, unless `c` re-ignites with `Ε`
+    StabilizationAbsorbs,
+    /// `Ω → c ≡ Ω` and `Ω c ≡ Ω`, unless `c` re-ignites with `Ε`
+    ClosureAbsorbs,
+}
+
+impl Law {
+    pub const ALL: [Law; 5] = [
+        Law::SimultaneityCommutes,
+        Law::CoexistenceCommutes,
+        Law::LoopIdempotent,
+        Law::StabilizationAbsorbs,
+        Law::ClosureAbsorbs,
+    ];
+
+    pub fn statement(&self) -> &'static str {
+        match self {
+            Law::SimultaneityCommutes => "a + b ≡ b + a",
+            Law::CoexistenceCommutes => "Σ(a, b) ≡ Σ(b, a)",
+            Law::LoopIdempotent => "[[a]] ≡ [a]",
+            Law::StabilizationAbsorbs => "(a = b) → c ≡ a = b",
+            Law::ClosureAbsorbs => "Ω → c ≡ Ω",
+        }
+    }
+}
+
+impl fmt::Display for Law {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} ({})", self, self.statement())
+    }
+}
+
+/// One application of a law, at the span of the node it rewrote in the original input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rewrite {
+    pub law: Law,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Canonical {
+    /// `None` when the input is empty or fails to execute.
+    pub expr: Option<Expr>,
+    /// Formatted canonical form.
+    pub text: String,
+    pub rewrites: Vec<Rewrite>,
+    /// Semantic errors that kept the input from being canonicalized.
+    pub errors: Vec<SemanticError>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Equivalence {
+    pub equivalent: bool,
+    pub left: Canonical,
+    pub right: Canonical,
+}
+
+impl Equivalence {
+    /// Distinct laws applied on either side, in declaration order.
+    pub fn laws(&self) -> Vec<Law> {
+        Law::ALL
+            .into_iter()
+            .filter(|l| self.left.rewrites.iter().chain(&self.right.rewrites).any(|r| r.law == *l))
+            .collect()
+    }
+}
+
+pub fn canonicalize(input: &str) -> Canonical {
+    let ast = parse(input);
+    let errors = execute_ast(&ast, input, GateRegistry::builtin_ref()).errors;
+    if !errors.is_empty() {
+        return Canonical { expr: None, text: String::new(), rewrites: Vec::new(), errors };
+    }
+    let mut rewrites = Vec::new();
+    let expr = ast.root.map(|e| rewrite(e, &mut rewrites));
+    let text = expr.as_ref().map(render).unwrap_or_default();
+    Canonical { expr, text, rewrites, errors }
+}
+
+pub fn equivalent(a: &str, b: &str) -> bool {
+    explain_equivalence(a, b).equivalent
+}
+
+/// Canonicalizes both sides and reports the laws used to reach each canonical form.
+pub fn explain_equivalence(a: &str, b: &str) -> Equivalence {
+    let left = canonicalize(a);
+    let right = canonicalize(b);
+    let equivalent = left.errors.is_empty() && right.errors.is_empty() && left.text == right.text;
+    Equivalence { equivalent, left, right }
+}
+
+/// The absorbing law that applies when `e` ends in a terminal (`Ω` or a completed `=`).
+fn terminal(e: &Expr) -> Option<Law> {
+    match &e.kind {
+        ExprKind::Symbol(s) if s.is("Ω") && s.modifiers.is_empty() => Some(Law::ClosureAbsorbs),
+        ExprKind::Sequence(items) => items.last().and_then(terminal),
+        ExprKind::Binary { op: Conditional::Stabilization, rhs, .. }
+            if !matches!(rhs.kind, ExprKind::Missing) =>
+        {
+            Some(Law::StabilizationAbsorbs)
+        }
+        ExprKind::Binary { op: Conditional::Flow, rhs, .. } => terminal(rhs),
+        _ => None,
+    }
+}
+
+/// Whether `e` starts with `Ε`, which re-ignites a closed or stabilized field.
+fn reignites(e: &Expr) -> bool {
+    match &e.kind {
+        ExprKind::Symbol(s) | ExprKind::Apply { head: s, .. } => s.is("Ε"),
+        ExprKind::Sequence(items) => items.first().is_some_and(reignites),
+        ExprKind::Binary { lhs, .. } => reignites(lhs),
+        _ => false,
+    }
+}
+
+fn sort_operands(items: &mut [Expr]) -> bool {
+    let before: Vec<String> = items.iter().map(render).collect();
+    items.sort_by_cached_key(render);
+    items.iter().map(render).ne(before)
+}
+
+fn rewrite(e: Expr, log: &mut Vec<Rewrite>) -> Expr {
+    let span = e.span;
+    match e.kind {
+        ExprKind::Binary { op: Conditional::Simultaneity, .. } => {
+            // Flatten the left-associated chain, canonicalize operands, then sort them.
+            let mut operands = Vec::new();
+            let mut op_spans = Vec::new();
+            let mut cur = e;
+            while let ExprKind::Binary { op: Conditional::Simultaneity, op_span, lhs, rhs } =
+                cur.kind
+            {
+                operands.push(*rhs);
+                op_spans.push(op_span);
+                cur = *lhs;
+            }
+            operands.push(cur);
+            operands.reverse();
+            op_spans.reverse();
+            let mut operands: Vec<Expr> = operands.into_iter().map(|o| rewrite(o, log)).collect();
+            if sort_operands(&mut operands) {
+                log.push(Rewrite { law: Law::SimultaneityCommutes, span });
+            }
+            let mut it = operands.into_iter();
+            let first = it.next().expect("chain has an operand");
+            it.zip(op_spans).fold(first, |lhs, (rhs, op_span)| {
+                let s = lhs.span.to(rhs.span);
+                Expr::new(
+                    ExprKind::Binary {
+                        op: Conditional::Simultaneity,
+                        op_span,
+                        lhs: Box::new(lhs),
+                        rhs: Box::new(rhs),
+                    },
+                    s,
+                )
+            })
+        }
+        ExprKind::Binary { op, op_span, lhs, rhs } => {
+            let lhs = rewrite(*lhs, log);
+            if matches!(op, Conditional::Flow | Conditional::Stabilization) {
+                if let Some(law) = terminal(&lhs).filter(|_| !reignites(&rhs)) {
+                    log.push(Rewrite { law, span });
+                    return lhs;
+                }
+            }
+            let rhs = rewrite(*rhs, log);
+            Expr::new(
+                ExprKind::Binary { op, op_span, lhs: Box::new(lhs), rhs: Box::new(rhs) },
+                span,
+            )
+        }
+        ExprKind::Apply { head, args } => {
+            let mut args: Vec<Expr> = args.into_iter().map(|a| rewrite(a, log)).collect();
+            if head.is("Σ") && sort_operands(&mut args) {
+                log.push(Rewrite { law: Law::CoexistenceCommutes, span });
+            }
+            Expr::new(ExprKind::Apply { head, args }, span)
+        }
+        ExprKind::Loop(Some(body)) => {
+            let body = rewrite(*body, log);
+            if matches!(body.kind, ExprKind::Loop(_)) {
+                log.push(Rewrite { law: Law::LoopIdempotent, span });
+                return Expr::new(body.kind, span);
+            }
+            Expr::new(ExprKind::Loop(Some(Box::new(body))), span)
+        }
+        ExprKind::Group(body) => Expr::new(ExprKind::Group(Box::new(rewrite(*body, log))), span),
+        ExprKind::Sequence(items) => {
+            let mut out = Vec::with_capacity(items.len());
+            // Items after a terminal are absorbed up to the next re-ignition.
+            let mut absorbing = None;
+            let mut absorbed = false;
+            for item in items {
+                if let Some(law) = absorbing.filter(|_| !reignites(&item)) {
+                    if !absorbed {
+                        log.push(Rewrite { law, span });
+                        absorbed = true;
+                    }
+                    continue;
+                }
+                let item = rewrite(item, log);
+                absorbing = terminal(&item);
+                absorbed = false;
+                out.push(item);
+            }
+            if out.len() == 1 {
+                out.pop().expect("one item")
+            } else {
+                Expr::new(ExprKind::Sequence(out), span)
+            }
+        }
+        kind => Expr::new(kind, span),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commutative_operands_are_ordered() {
+        let eq = explain_equivalence("Σ + Ψ", "Ψ + Σ");
+        assert!(eq.equivalent);
+        assert_eq!(eq.laws(), vec![Law::SimultaneityCommutes]);
+        assert!(equivalent("Σ(Ψ, Γ) + Φ", "Φ + Σ(Γ,Ψ)"));
+        assert!(!equivalent("Γ + Ψ + Δ", "Ψ + (Γ + Δ)"));
+    }
+
+    #[test]
+    fn nested_loops_collapse() {
+        let c = canonicalize("[[[ΔΨ]]] → Ξ");
+        assert_eq!(c.text, "[Δ Ψ] → Ξ");
+        assert_eq!(c.rewrites.iter().filter(|r| r.law == Law::LoopIdempotent).count(), 2);
+    }
+
+    #[test]
+    fn terminals_absorb() {
+        assert_eq!(canonicalize("Δ Ω → Ψ").text, "Δ Ω");
+        assert_eq!(canonicalize("Ψ Ω Ω").text, "Ψ Ω");
+        assert_eq!(canonicalize("Ψ = Ω = Γ").text, "Ψ = Ω");
+        assert_eq!(canonicalize("Δ = Ψ → Γ").text, "Δ = Ψ → Γ");
+        assert!(equivalent("Δ Λ Ψ Ω", "Δ Λ Ψ Ω → Γ"));
+    }
+
+    #[test]
+    fn reignition_stops_absorption() {
+        assert_eq!(canonicalize("Ψ Ω Ε Π").text, "Ψ Ω Ε Π");
+        assert_eq!(canonicalize("Ψ Ω Γ Ε Π").text, "Ψ Ω Ε Π");
+        assert_eq!(canonicalize("Ψ Ω → Ε Π").text, "Ψ Ω → Ε Π");
+        assert!(!equivalent("Ψ Ω Ε Π", "Ψ Ω"));
+    }
+
+    #[test]
+    fn failing_expressions_have_no_canonical_form() {
+        let c = canonicalize("Ψ Ω Π");
+        assert!(c.expr.is_none() && c.rewrites.is_empty());
+        assert_eq!(c.errors.len(), 1);
+        assert!(!equivalent("Ψ Ω Π", "Ψ Ω"));
+        assert!(!equivalent("Ψ Ω Π", "Ψ Ω Π"));
+    }
+
+    #[test]
+    fn distinct_expressions_differ() {
+        let eq = explain_equivalence("Δ → Ψ", "Ψ → Δ");
+        assert!(!eq.equivalent);
+        assert!(eq.laws().is_empty());
+    }
+}
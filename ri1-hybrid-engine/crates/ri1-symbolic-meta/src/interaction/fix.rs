@@ -0,0 +1,109 @@
+//! Applying the machine-applicable [`Suggestion`]s attached to interaction findings.
+
+use ri1_core::constraints::Suggestion;
+
+use super::rules::ValidatorConfig;
+use super::validate::validate_interactions;
+
+/// Re-validation rounds before giving up on reaching a fixed point.
+const MAX_PASSES: usize = 8;
+
+#[derive(Debug, Clone, Default)]
+pub struct Fixed {
+    pub output: String,
+    pub applied: Vec<Suggestion>,
+}
+
+/// Applies `suggestions` in order, skipping any whose edits overlap an earlier one.
+/// Returns the edited content and the suggestions that were applied.
+pub fn apply_suggestions(content: &str, suggestions: &[Suggestion]) -> (String, Vec<Suggestion>) {
+    let mut taken: Vec<(usize, usize)> = Vec::new();
+    let mut applied = Vec::new();
+    let overlaps = |a: (usize, usize), b: (usize, usize)| a.0 < b.1 && b.0 < a.1 || a.0 == b.0;
+    for s in suggestions {
+        let ranges: Vec<_> = s.edits.iter().map(|e| (e.span.start, e.span.end)).collect();
+        let in_bounds = ranges.iter().all(|r| r.0 <= r.1 && r.1 <= content.len());
+        if !in_bounds || ranges.iter().any(|r| taken.iter().any(|t| overlaps(*r, *t))) {
+            continue;
+        }
+        taken.extend(ranges);
+        applied.push(s.clone());
+    }
+    let mut edits: Vec<_> = applied.iter().flat_map(|s| &s.edits).collect();
+    edits.sort_by_key(|e| std::cmp::Reverse(e.span.start));
+    let mut out = content.to_string();
+    for e in edits {
+        out.replace_range(e.span.start..e.span.end, &e.replacement);
+    }
+    (out, applied)
+}
+
+/// Repeatedly applies the first safe suggestion of each finding until none remain.
+pub fn fix_expr(content: &str, cfg: &ValidatorConfig) -> Fixed {
+    let mut fixed = Fixed { output: content.to_string(), applied: Vec::new() };
+    for _ in 0..MAX_PASSES {
+        let safe: Vec<Suggestion> = validate_interactions(&fixed.output, cfg)
+            .into_iter()
+            .filter_map(|e| e.suggestions.into_iter().find(|s| s.safe))
+            .collect();
+        if safe.is_empty() {
+            break;
+        }
+        let (out, applied) = apply_suggestions(&fixed.output, &safe);
+        fixed.output = out;
+        fixed.applied.extend(applied);
+    }
+    fixed
+}
+
+/// [`fix_expr`] over a document with one expression per line.
+pub fn fix_source(src: &str, cfg: &ValidatorConfig) -> Fixed {
+    let mut fixed = Fixed::default();
+    for line in src.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let f = fix_expr(body, cfg);
+        fixed.output.push_str(&f.output);
+        fixed.output.push_str(&line[body.len()..]);
+        fixed.applied.extend(f.applied);
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(src: &str) -> String {
+        fix_source(src, &ValidatorConfig::default()).output
+    }
+
+    #[test]
+    fn applies_safe_fixes() {
+        assert_eq!(fix("Δ Λ Ψ Ω ="), "Δ Λ Ψ Ω");
+        assert_eq!(fix("Ψ :"), "Ψ");
+        assert_eq!(fix("[Δ → [Ψ] "), "[Δ → [Ψ]] ");
+        assert_eq!(fix("Δ Ψ] → Ω"), "Δ Ψ → Ω");
+        assert_eq!(fix("Δ Ω → Π"), "Δ Π Ω");
+    }
+
+    #[test]
+    fn leaves_unsafe_fixes_and_other_lines() {
+        assert_eq!(fix("Δ Ω + Π"), "Δ Ω + Π");
+        assert_eq!(fix("Ψ : Φ\n[Δ\n\nΨ =\n"), "Ψ : Φ\n[Δ]\n\nΨ\n");
+    }
+
+    #[test]
+    fn disabled_rules_offer_no_fixes() {
+        let cfg =
+            ValidatorConfig::from_toml_str("[rules.equals-terminal]\nenabled = false\n").unwrap();
+        assert_eq!(fix_source("Ψ =\nΨ :\n", &cfg).output, "Ψ =\nΨ\n");
+    }
+
+    #[test]
+    fn overlapping_suggestions_apply_once() {
+        let ev = validate_interactions("Ψ =", &ValidatorConfig::default());
+        let s = ev.into_iter().flat_map(|e| e.suggestions).next().unwrap();
+        let (out, applied) = apply_suggestions("Ψ =", &[s.clone(), s]);
+        assert_eq!((out.as_str(), applied.len()), ("Ψ", 1));
+    }
+}
@@ -0,0 +1,267 @@
+//! Causal flow graph over `→` arcs.
+//!
+//! Each `→` adds an edge from the operands that end its left side to the operands that start
+//! its right side; operators are nodes, identified by glyph. A juxtaposed sequence enters at
+//! its first operand and leaves at its last, while the other conditionals expose the operands
+//! of both sides. Strongly connected components give the causal cycles; a cycle whose arcs
+//! all sit inside the same outermost `[]` is a declared loop rather than a fault.
+
+use super::ast::{Ast, Conditional, Expr, ExprKind, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowEdge {
+    pub from: usize,
+    pub to: usize,
+    /// The `→` that produced the edge.
+    pub arrow: Span,
+    /// Outermost `[]` enclosing the arrow, if any.
+    pub scope: Option<Span>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowCycle {
+    /// Node glyphs along the cycle, starting and ending at the same node.
+    pub path: Vec<String>,
+    /// Arrows traversed, one per step of `path`.
+    pub arrows: Vec<Span>,
+    /// Every arc of the component lies within one `[]` loop container.
+    pub enclosed: bool,
+}
+
+impl FlowCycle {
+    /// `Ψ → Δ → Ψ`
+    pub fn describe(&self) -> String {
+        self.path.join(" → ")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FlowGraph {
+    pub nodes: Vec<String>,
+    pub edges: Vec<FlowEdge>,
+}
+
+impl FlowGraph {
+    pub fn from_ast(ast: &Ast) -> Self {
+        let mut g = FlowGraph::default();
+        if let Some(root) = &ast.root {
+            g.ends(root, None);
+        }
+        g
+    }
+
+    fn node(&mut self, glyph: String) -> usize {
+        match self.nodes.iter().position(|n| *n == glyph) {
+            Some(i) => i,
+            None => {
+                self.nodes.push(glyph);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Adds the edges inside `e` and returns its (entry, exit) nodes.
+    fn ends(&mut self, e: &Expr, scope: Option<Span>) -> (Vec<usize>, Vec<usize>) {
+        match &e.kind {
+            ExprKind::Symbol(s) => {
+                let n = self.node(s.glyph());
+                (vec![n], vec![n])
+            }
+            ExprKind::Apply { head, args } => {
+                for a in args {
+                    self.ends(a, scope);
+                }
+                let n = self.node(head.glyph());
+                (vec![n], vec![n])
+            }
+            ExprKind::Loop(Some(body)) => self.ends(body, scope.or(Some(e.span))),
+            ExprKind::Group(body) => self.ends(body, scope),
+            ExprKind::Sequence(items) => {
+                let mut all: Vec<_> = items.iter().map(|i| self.ends(i, scope)).collect();
+                let exit = all.pop().map(|(_, x)| x).unwrap_or_default();
+                let entry = all.into_iter().next().map(|(n, _)| n).unwrap_or_else(|| exit.clone());
+                (entry, exit)
+            }
+            ExprKind::Binary { op: Conditional::Flow, op_span, lhs, rhs } => {
+                let (entry, from) = self.ends(lhs, scope);
+                let (to, exit) = self.ends(rhs, scope);
+                for &f in &from {
+                    for &t in &to {
+                        self.edges.push(FlowEdge { from: f, to: t, arrow: *op_span, scope });
+                    }
+                }
+                (entry, exit)
+            }
+            ExprKind::Binary { lhs, rhs, .. } => {
+                let (mut entry, mut exit) = self.ends(lhs, scope);
+                let (e2, x2) = self.ends(rhs, scope);
+                entry.extend(e2);
+                exit.extend(x2);
+                (entry, exit)
+            }
+            ExprKind::Loop(None) | ExprKind::Text(_) | ExprKind::Missing => {
+                (Vec::new(), Vec::new())
+            }
+        }
+    }
+
+    /// One cycle per cyclic strongly connected component, in order of first appearance.
+    pub fn cycles(&self) -> Vec<FlowCycle> {
+        let mut out: Vec<FlowCycle> = self
+            .components()
+            .into_iter()
+            .filter_map(|comp| {
+                let inner: Vec<&FlowEdge> = self
+                    .edges
+                    .iter()
+                    .filter(|e| comp.contains(&e.from) && comp.contains(&e.to))
+                    .collect();
+                let start = *comp.iter().min()?;
+                let steps = self.path_back(start, &inner)?;
+                let enclosed =
+                    inner[0].scope.is_some() && inner.iter().all(|e| e.scope == inner[0].scope);
+                let mut path = vec![self.nodes[start].clone()];
+                path.extend(steps.iter().map(|e| self.nodes[e.to].clone()));
+                Some(FlowCycle { path, arrows: steps.iter().map(|e| e.arrow).collect(), enclosed })
+            })
+            .collect();
+        out.sort_by_key(|c| c.arrows.first().map(|a| a.start));
+        out
+    }
+
+    /// Shortest edge path from `start` back to itself using only `edges`.
+    fn path_back<'e>(&self, start: usize, edges: &[&'e FlowEdge]) -> Option<Vec<&'e FlowEdge>> {
+        let mut via: Vec<Option<&FlowEdge>> = vec![None; self.nodes.len()];
+        let mut queue = std::collections::VecDeque::from([start]);
+        let mut reached = false;
+        while let Some(n) = queue.pop_front() {
+            for e in edges.iter().filter(|e| e.from == n) {
+                if e.to == start {
+                    via[start] = Some(e);
+                    reached = true;
+                    break;
+                }
+                if via[e.to].is_none() {
+                    via[e.to] = Some(e);
+                    queue.push_back(e.to);
+                }
+            }
+            if reached {
+                break;
+            }
+        }
+        let mut steps = vec![via[start]?];
+        while steps.last()?.from != start {
+            steps.push(via[steps.last()?.from]?);
+        }
+        steps.reverse();
+        Some(steps)
+    }
+
+    /// Tarjan's algorithm; keeps only components that contain a cycle.
+    fn components(&self) -> Vec<Vec<usize>> {
+        struct Tarjan<'g> {
+            g: &'g FlowGraph,
+            index: Vec<Option<usize>>,
+            low: Vec<usize>,
+            stack: Vec<usize>,
+            on_stack: Vec<bool>,
+            next: usize,
+            out: Vec<Vec<usize>>,
+        }
+        impl Tarjan<'_> {
+            fn visit(&mut self, v: usize) {
+                self.index[v] = Some(self.next);
+                self.low[v] = self.next;
+                self.next += 1;
+                self.stack.push(v);
+                self.on_stack[v] = true;
+                for w in self.g.edges.iter().filter(|e| e.from == v).map(|e| e.to) {
+                    match self.index[w] {
+                        None => {
+                            self.visit(w);
+                            self.low[v] = self.low[v].min(self.low[w]);
+                        }
+                        Some(i) if self.on_stack[w] => self.low[v] = self.low[v].min(i),
+                        Some(_) => {}
+                    }
+                }
+                if Some(self.low[v]) == self.index[v] {
+                    let mut comp = Vec::new();
+                    while let Some(w) = self.stack.pop() {
+                        self.on_stack[w] = false;
+                        comp.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    let self_loop = self.g.edges.iter().any(|e| e.from == v && e.to == v);
+                    if comp.len() > 1 || self_loop {
+                        self.out.push(comp);
+                    }
+                }
+            }
+        }
+        let n = self.nodes.len();
+        let mut t = Tarjan {
+            g: self,
+            index: vec![None; n],
+            low: vec![0; n],
+            stack: Vec::new(),
+            on_stack: vec![false; n],
+            next: 0,
+            out: Vec::new(),
+        };
+        for v in 0..n {
+            if t.index[v].is_none() {
+                t.visit(v);
+            }
+        }
+        t.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interaction::parse::parse;
+
+    fn cycles(src: &str) -> Vec<FlowCycle> {
+        FlowGraph::from_ast(&parse(src)).cycles()
+    }
+
+    #[test]
+    fn acyclic_chains_have_no_cycles() {
+        assert!(cycles("Ψ → Δ → Ξ → Ω").is_empty());
+        assert!(cycles("Ε Δ → Φ : Ψ → Ω").is_empty());
+    }
+
+    #[test]
+    fn reports_exact_path() {
+        let src = "Ψ → Δ → Ξ → Ψ";
+        let c = cycles(src);
+        assert_eq!(c.len(), 1);
+        assert_eq!(c[0].describe(), "Ψ → Δ → Ξ → Ψ");
+        assert_eq!(c[0].arrows.len(), 3);
+        assert!(!c[0].enclosed);
+        assert_eq!(&src[c[0].arrows[2].start..c[0].arrows[2].end], "→");
+        assert_eq!(cycles("Γ → Γ")[0].describe(), "Γ → Γ");
+    }
+
+    #[test]
+    fn loops_enclose_cycles() {
+        assert!(cycles("[Ψ → Δ → Ψ] → Ω").iter().all(|c| c.enclosed));
+        assert!(cycles("[[Ψ → Δ] → Ψ]").iter().all(|c| c.enclosed));
+        // The arc back into Ψ leaves the loop.
+        let c = cycles("[Ψ → Δ] → Ψ");
+        assert_eq!(c.len(), 1);
+        assert!(!c[0].enclosed);
+    }
+
+    #[test]
+    fn operands_on_both_sides_of_conditionals_flow() {
+        let c = cycles("Ψ + Γ → Δ → Γ");
+        assert_eq!(c[0].describe(), "Γ → Δ → Γ");
+        assert!(cycles("Ψ Δ → Ψ").is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+//! Canonical formatting of Phipe expressions.
+//!
+//! Style: one space around every conditional (`Ψ : Φ → Ω`), single spaces between
+//! juxtaposed operands (`Ε Δ Φ`), no padding inside `[]` and `()`, and applications written
+//! `Σ(Ψ, Γ)`. Combining marks and superscripts stay attached to their symbol and text runs are
+//! kept verbatim. Output re-parses to the same tree, so formatting is idempotent.
+
+use std::fmt;
+
+use super::ast::{Expr, ExprKind, ParseError, ParseErrorKind, Symbol};
+use super::parse::parse;
+
+/// Input that cannot be formatted without changing its structure, e.g. an unclosed `[`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    /// 1-based line of the offending expression.
+    pub line: usize,
+    /// Structural errors, with spans relative to that line.
+    pub errors: Vec<ParseError>,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msgs = self.errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>();
+        write!(f, "line {}: {}", self.line, msgs.join("; "))
+    }
+}
+
+/// Formats a single expression. Missing operands (e.g. a trailing `=`) are preserved as
+/// written; unbalanced brackets or stray separators are reported instead of repaired.
+pub fn format_expr(input: &str) -> Result<String, FormatError> {
+    let ast = parse(input);
+    let errors: Vec<ParseError> =
+        ast.errors.into_iter().filter(|e| e.kind != ParseErrorKind::MissingOperand).collect();
+    if !errors.is_empty() {
+        return Err(FormatError { line: 1, errors });
+    }
+    Ok(ast.root.as_ref().map(render).unwrap_or_default())
+}
+
+/// Formats a document with one expression per line, keeping blank lines and each line's
+/// terminator (`\n`, `\r\n` or none).
+pub fn format_source(src: &str) -> Result<String, FormatError> {
+    let mut out = String::with_capacity(src.len());
+    for (i, line) in src.split_inclusive('\n').enumerate() {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let formatted = format_expr(body).map_err(|e| FormatError { line: i + 1, ..e })?;
+        out.push_str(&formatted);
+        out.push_str(&line[body.len()..]);
+    }
+    Ok(out)
+}
+
+fn symbol(s: &Symbol) -> String {
+    let mut out = s.glyph();
+    out.extend(s.modifiers.iter().map(|m| m.glyph));
+    out
+}
+
+fn join(parts: impl IntoIterator<Item = String>, sep: &str) -> String {
+    parts.into_iter().filter(|p| !p.is_empty()).collect::<Vec<_>>().join(sep)
+}
+
+pub(crate) fn render(e: &Expr) -> String {
+    match &e.kind {
+        ExprKind::Symbol(s) => symbol(s),
+        ExprKind::Text(t) => t.clone(),
+        ExprKind::Apply { head, args } => {
+            format!("{}({})", symbol(head), args.iter().map(render).collect::<Vec<_>>().join(", "))
+        }
+        ExprKind::Loop(body) => format!("[{}]", body.as_deref().map(render).unwrap_or_default()),
+        ExprKind::Group(body) => format!("({})", render(body)),
+        ExprKind::Sequence(items) => join(items.iter().map(render), " "),
+        ExprKind::Binary { op, lhs, rhs, .. } => {
+            join([render(lhs), op.symbol().to_string(), render(rhs)], " ")
+        }
+        ExprKind::Missing => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_spacing() {
+        assert_eq!(format_expr("ΕΔΦ→Ω").unwrap(), "Ε Δ Φ → Ω");
+        assert_eq!(format_expr("  Δ Λ  Ψ Ω   =").unwrap(), "Δ Λ Ψ Ω =");
+        assert_eq!(format_expr("[ ΔΨ ]→Ξ=Ω").unwrap(), "[Δ Ψ] → Ξ = Ω");
+        assert_eq!(format_expr("Σ( Ψ ,Γ )+ζ(Ψⁿ)").unwrap(), "Σ(Ψ, Γ) + ζ(Ψⁿ)");
+        assert_eq!(format_expr(":|/").unwrap(), ": | /");
+        assert_eq!(format_expr("Γ̇ⁿ:Σ (Ψ)").unwrap(), "Γ̇ⁿ : Σ (Ψ)");
+    }
+
+    #[test]
+    fn is_idempotent() {
+        for src in ["ΕΔΦ → Ω", "Δ Λ Ψ Ω =", "Ψ:Φ/Ψ|Γ=Ω", "[[Δ]Ψ]→(Σ+Ψ)", "TEXT: Φ+Σ", "Σ(Ψ,[Γ→Δ])"]
+        {
+            let once = format_expr(src).unwrap();
+            assert_eq!(format_expr(&once).unwrap(), once, "not idempotent for {:?}", src);
+        }
+    }
+
+    #[test]
+    fn refuses_structural_errors() {
+        let err = format_source("Ψ : Φ\n[Δ → Ψ").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(format_expr("Δ]").is_err());
+    }
+
+    #[test]
+    fn formats_documents_line_by_line() {
+        assert_eq!(format_source("Ψ:Φ\n\nΔ→Ω\n").unwrap(), "Ψ : Φ\n\nΔ → Ω\n");
+        assert_eq!(format_source("Ψ:Φ").unwrap(), "Ψ : Φ");
+        assert_eq!(format_source("Ψ:Φ\r\n\r\nΔ→Ω\n").unwrap(), "Ψ : Φ\r\n\r\nΔ → Ω\n");
+    }
+}
@@ -0,0 +1,280 @@
+//! Step-by-step execution of Phipe expressions over a [`FieldState`].
+//!
+//! Operators run in source order: sequences and conditionals left to right, an application's
+//! head before its arguments, and a `[]` body once, one recursion level deeper. Each operator
+//! passes through its gate and updates the field; the trace records the state after every
+//! step. Semantic errors are found by what the field has already been through, e.g. a Δ on a
+//! field an earlier Δ collapsed irreversibly, rather than by scanning the text.
+
+use ri1_core::constraints::GateContext;
+
+use super::ast::{Ast, Conditional, Expr, ExprKind, Span, Symbol};
+use super::neighborhood::gate_contexts;
+use super::parse::parse;
+use crate::gates::GateRegistry;
+
+/// Operators that transform the field and so cannot follow closure or stabilization.
+const TRANSFORMS: [&str; 3] = ["Δ", "Ξ", "Π"];
+/// Operators that put a resting field into motion when no Ε precedes them.
+const ACTIVATING: [&str; 8] = ["Δ", "δ", "Ψ", "Γ", "Γ̇", "Ξ", "Π", "ω"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Motion {
+    #[default]
+    Rest,
+    Active,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FieldState {
+    pub motion: Motion,
+    /// A Δ fusion collapsed the field.
+    pub collapsed: bool,
+    /// The collapse cannot be undone; further fusion or coexistence is an error.
+    pub irreversible: bool,
+    /// Ω closed the field; only Ε re-ignition reopens it.
+    pub closed: bool,
+    /// An `=` resolved the field into its final form.
+    pub stabilized: bool,
+    /// Recursion threads linked by λ.
+    pub entangled_threads: usize,
+    /// Current `[]` nesting.
+    pub depth: usize,
+    pub max_depth: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemanticErrorKind {
+    /// Δ/Ξ/Π after `=` stabilization.
+    AfterStabilization,
+    /// Δ/Ξ/Π after Ω closure without re-ignition.
+    AfterClosure,
+    /// Δ or Σ on a field already collapsed by an irreversible Δ.
+    AfterCollapse,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticError {
+    pub kind: SemanticErrorKind,
+    pub message: String,
+    /// The operator that acted.
+    pub symbol: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    /// Operator glyph, or `=` for a stabilization step.
+    pub symbol: String,
+    pub span: Span,
+    pub note: Option<String>,
+    /// Field state after the step.
+    pub state: FieldState,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Execution {
+    pub trace: Vec<TraceStep>,
+    pub state: FieldState,
+    pub errors: Vec<SemanticError>,
+}
+
+/// Executes `content`, passing each operator through its gate in `gates`.
+pub fn execute(content: &str, gates: &GateRegistry) -> Execution {
+    execute_ast(&parse(content), content, gates)
+}
+
+/// Executes an already parsed expression; `content` is handed to the gates.
+pub fn execute_ast(ast: &Ast, content: &str, gates: &GateRegistry) -> Execution {
+    let contexts = gate_contexts(ast, content).into_iter().map(|(_, cx)| cx).collect();
+    let mut run = Run { content, gates, contexts, next: 0, exec: Execution::default() };
+    if let Some(root) = &ast.root {
+        run.expr(root, None, None);
+    }
+    run.exec
+}
+
+struct Run<'a> {
+    content: &'a str,
+    gates: &'a GateRegistry,
+    /// Gate contexts in source order, which is also execution order.
+    contexts: Vec<GateContext<'a>>,
+    next: usize,
+    exec: Execution,
+}
+
+impl Run<'_> {
+    fn expr(&mut self, e: &Expr, prev: Option<&Expr>, next: Option<&Expr>) {
+        match &e.kind {
+            ExprKind::Symbol(s) => self.step(s, prev, next, 0),
+            ExprKind::Apply { head, args } => {
+                self.step(head, None, None, args.len());
+                for a in args {
+                    self.expr(a, None, None);
+                }
+            }
+            ExprKind::Loop(body) => {
+                let st = &mut self.exec.state;
+                st.depth += 1;
+                st.max_depth = st.max_depth.max(st.depth);
+                if let Some(b) = body {
+                    self.expr(b, None, None);
+                }
+                self.exec.state.depth -= 1;
+            }
+            ExprKind::Group(body) => self.expr(body, None, None),
+            ExprKind::Sequence(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let before = i.checked_sub(1).map(|j| &items[j]);
+                    self.expr(item, before, items.get(i + 1));
+                }
+            }
+            ExprKind::Binary { op, op_span, lhs, rhs } => {
+                self.expr(lhs, None, None);
+                if *op == Conditional::Stabilization {
+                    self.exec.state.stabilized = true;
+                    self.record(
+                        "=",
+                        *op_span,
+                        Some("= resolves the field into its final form".into()),
+                    );
+                }
+                self.expr(rhs, None, None);
+            }
+            ExprKind::Text(_) | ExprKind::Missing => {}
+        }
+    }
+
+    fn step(&mut self, s: &Symbol, prev: Option<&Expr>, next: Option<&Expr>, args: usize) {
+        let glyph = s.glyph();
+        let g = glyph.as_str();
+        self.check(g, s.span);
+        let cx =
+            self.contexts.get(self.next).cloned().unwrap_or_else(|| GateContext::new(self.content));
+        self.next += 1;
+        let out = self.gates.gate_for(g).map(|gate| gate.apply(&cx));
+        let mut note = out.as_ref().and_then(|o| o.note.clone());
+        let st = &mut self.exec.state;
+        match g {
+            "Ε" => {
+                if st.closed {
+                    st.closed = false;
+                    note = Some("Ε re-ignites a closed field".into());
+                }
+                st.motion = Motion::Active;
+            }
+            "ε" if st.motion == Motion::Rest => {
+                note = Some("ε at rest: micro-activation needs an active field; ignored".into());
+            }
+            "Δ" => {
+                // The Δ gate decides whether the fusion happened (a Φ beside Δ prevents it) and
+                // whether it can be undone.
+                if let Some(out) = &out {
+                    st.collapsed |= !out.prevented_fusion;
+                    st.irreversible |= !out.prevented_disruption;
+                }
+            }
+            "Ω" => {
+                st.closed = true;
+                st.motion = Motion::Rest;
+            }
+            "λ" => {
+                // Binds its arguments, or the operands on either side when juxtaposed.
+                st.entangled_threads +=
+                    if args > 0 { args } else { prev.is_some() as usize + next.is_some() as usize };
+            }
+            _ => {}
+        }
+        if ACTIVATING.contains(&g) && !st.closed {
+            st.motion = Motion::Active;
+        }
+        self.record(g, s.span, note);
+    }
+
+    /// Reports `glyph` acting on a field whose history forbids it.
+    fn check(&mut self, glyph: &str, span: Span) {
+        let st = &self.exec.state;
+        let (kind, message) = if TRANSFORMS.contains(&glyph) && st.stabilized {
+            (
+                SemanticErrorKind::AfterStabilization,
+                format!("{} acts after '=' stabilized the field", glyph),
+            )
+        } else if TRANSFORMS.contains(&glyph) && st.closed {
+            (
+                SemanticErrorKind::AfterClosure,
+                format!("{} acts after Ω closed the field; re-ignite with Ε first", glyph),
+            )
+        } else if (glyph == "Δ" || glyph == "Σ") && st.irreversible {
+            (
+                SemanticErrorKind::AfterCollapse,
+                format!("{} acts on a field already collapsed by an irreversible Δ", glyph),
+            )
+        } else {
+            return;
+        };
+        self.exec.errors.push(SemanticError { kind, message, symbol: glyph.to_string(), span });
+    }
+
+    fn record(&mut self, symbol: &str, span: Span, note: Option<String>) {
+        let state = self.exec.state.clone();
+        self.exec.trace.push(TraceStep { symbol: symbol.to_string(), span, note, state });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(x: &Execution) -> Vec<&str> {
+        x.trace.iter().map(|s| s.symbol.as_str()).collect()
+    }
+
+    fn execute(content: &str) -> Execution {
+        super::execute(content, &GateRegistry::builtin())
+    }
+
+    #[test]
+    fn traces_in_execution_order() {
+        let x = execute("Ε [Δ Ψ] → Ξ = Ω");
+        assert_eq!(symbols(&x), ["Ε", "Δ", "Ψ", "Ξ", "=", "Ω"]);
+        assert_eq!(x.trace[0].state.motion, Motion::Active);
+        assert_eq!(x.trace[1].state.depth, 1);
+        assert!(x.trace[1].state.collapsed && x.trace[1].state.irreversible);
+        assert!(x.state.closed && x.state.stabilized);
+        assert_eq!((x.state.motion, x.state.depth, x.state.max_depth), (Motion::Rest, 0, 1));
+        assert!(x.errors.is_empty());
+    }
+
+    #[test]
+    fn acting_after_irreversible_collapse() {
+        let src = "Δ Λ Σ(Ψ, Γ)";
+        let x = execute(src);
+        assert_eq!(x.errors.len(), 1);
+        assert_eq!(x.errors[0].kind, SemanticErrorKind::AfterCollapse);
+        assert_eq!(&src[x.errors[0].span.start..x.errors[0].span.end], "Σ");
+
+        // Φ next to Δ prevents the fusion, so nothing collapses.
+        let x = execute("Φ Δ → Ψ Δ Φ");
+        assert!(!x.state.collapsed);
+        assert_eq!(x.errors.len(), 0);
+    }
+
+    #[test]
+    fn closure_and_stabilization_are_terminal() {
+        let x = execute("Δ Ω → Π");
+        assert_eq!(
+            x.errors.iter().map(|e| e.kind).collect::<Vec<_>>(),
+            [SemanticErrorKind::AfterClosure]
+        );
+        assert!(execute("Ψ Ω Ε Π").errors.is_empty());
+        let x = execute("Ψ = Ξ");
+        assert_eq!(x.errors[0].kind, SemanticErrorKind::AfterStabilization);
+    }
+
+    #[test]
+    fn entanglement_and_rest() {
+        let x = execute("Ψ λ Γ → λ(Ψ, Γ, Ξ)");
+        assert_eq!(x.state.entangled_threads, 5);
+        assert_eq!(execute("Φ ε").state.motion, Motion::Rest);
+    }
+}
@@ -1,3 +1,12 @@
+pub mod ast;
+pub mod canon;
+pub mod fix;
+pub mod flow;
+pub mod format;
+pub mod interpret;
+pub mod neighborhood;
+pub mod normalize;
+pub mod parse;
 pub mod rules;
 pub mod tokenize;
 pub mod validate;
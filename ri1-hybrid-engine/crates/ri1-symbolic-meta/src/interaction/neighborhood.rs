@@ -0,0 +1,133 @@
+//! Parsed neighbourhood of each operator occurrence, handed to its gate as a [`GateContext`].
+
+use ri1_core::constraints::GateContext;
+
+use super::ast::{Ast, Conditional, Expr, ExprKind, Symbol};
+
+struct Occurrence<'e> {
+    symbol: &'e Symbol,
+    thread: usize,
+    loop_depth: usize,
+    phi_boundary: bool,
+}
+
+/// Every operator occurrence in source order (an application's head before its arguments)
+/// with its gate context.
+pub fn gate_contexts<'e, 'a>(ast: &'e Ast, content: &'a str) -> Vec<(&'e Symbol, GateContext<'a>)> {
+    let mut occ = Vec::new();
+    let mut threads = 0;
+    if let Some(root) = &ast.root {
+        collect(root, 0, &mut threads, 0, None, &mut occ);
+    }
+    let glyphs: Vec<String> = occ.iter().map(|o| o.symbol.glyph()).collect();
+    occ.iter()
+        .enumerate()
+        .map(|(i, o)| {
+            let same = |j: &usize| occ[*j].thread == o.thread;
+            let cx = GateContext {
+                content,
+                span: Some(o.symbol.span.locate(content)),
+                preceding: (0..i).rev().filter(same).map(|j| glyphs[j].clone()).collect(),
+                following: (i + 1..occ.len()).filter(same).map(|j| glyphs[j].clone()).collect(),
+                loop_depth: o.loop_depth,
+                phi_boundary: o.phi_boundary,
+            };
+            (o.symbol, cx)
+        })
+        .collect()
+}
+
+/// Contexts of every occurrence of any of `glyphs`, in source order, or a single empty
+/// context when none was parsed.
+pub fn contexts_of<'a>(
+    contexts: &[(&Symbol, GateContext<'a>)],
+    glyphs: &[&str],
+    content: &'a str,
+) -> Vec<GateContext<'a>> {
+    let found: Vec<_> = contexts
+        .iter()
+        .filter(|(s, _)| glyphs.iter().any(|g| s.is(g)))
+        .map(|(_, cx)| cx.clone())
+        .collect();
+    if found.is_empty() {
+        vec![GateContext::new(content)]
+    } else {
+        found
+    }
+}
+
+fn collect<'e>(
+    e: &'e Expr,
+    thread: usize,
+    threads: &mut usize,
+    depth: usize,
+    siblings: Option<(Option<&Expr>, Option<&Expr>)>,
+    out: &mut Vec<Occurrence<'e>>,
+) {
+    let is_phi = |x: Option<&Expr>| x.and_then(Expr::head_symbol).is_some_and(|s| s.is("Φ"));
+    let phi_boundary = siblings.is_some_and(|(p, n)| is_phi(p) || is_phi(n));
+    match &e.kind {
+        ExprKind::Symbol(s) => {
+            out.push(Occurrence { symbol: s, thread, loop_depth: depth, phi_boundary })
+        }
+        ExprKind::Apply { head, args } => {
+            out.push(Occurrence { symbol: head, thread, loop_depth: depth, phi_boundary });
+            for a in args {
+                collect(a, thread, threads, depth, None, out);
+            }
+        }
+        ExprKind::Loop(Some(body)) => collect(body, thread, threads, depth + 1, None, out),
+        ExprKind::Group(body) => collect(body, thread, threads, depth, None, out),
+        ExprKind::Sequence(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let prev = i.checked_sub(1).map(|j| &items[j]);
+                collect(item, thread, threads, depth, Some((prev, items.get(i + 1))), out);
+            }
+        }
+        ExprKind::Binary { op, lhs, rhs, .. } => {
+            collect(lhs, thread, threads, depth, None, out);
+            // Orthogonal fields do not share a thread.
+            let rhs_thread = if *op == Conditional::Orthogonality {
+                *threads += 1;
+                *threads
+            } else {
+                thread
+            };
+            collect(rhs, rhs_thread, threads, depth, None, out);
+        }
+        ExprKind::Loop(None) | ExprKind::Text(_) | ExprKind::Missing => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interaction::parse::parse;
+
+    #[test]
+    fn neighbours_follow_threads() {
+        let src = "Ψ → [Δ Φ] | Λ Ω";
+        let ast = parse(src);
+        let cx = gate_contexts(&ast, src);
+        let glyphs: Vec<String> = cx.iter().map(|(s, _)| s.glyph()).collect();
+        assert_eq!(glyphs, ["Ψ", "Δ", "Φ", "Λ", "Ω"]);
+        let (_, delta) = &cx[1];
+        assert_eq!(
+            (delta.preceding.clone(), delta.following.clone()),
+            (vec!["Ψ".to_string()], vec!["Φ".to_string()])
+        );
+        assert_eq!(delta.loop_depth, 1);
+        assert!(delta.phi_boundary);
+        let (_, lambda) = &cx[3];
+        assert!(lambda.preceding.is_empty() && lambda.followed_by(&["Ω"]));
+    }
+
+    #[test]
+    fn phi_across_a_conditional_is_no_boundary() {
+        let src = "Φ + Δ Ψ";
+        let ast = parse(src);
+        let delta = &contexts_of(&gate_contexts(&ast, src), &["Δ"], src)[0];
+        assert!(!delta.phi_boundary && delta.preceded_by(&["Φ"]));
+        assert!(contexts_of(&gate_contexts(&ast, src), &["Ω"], src)[0].span.is_none());
+    }
+}
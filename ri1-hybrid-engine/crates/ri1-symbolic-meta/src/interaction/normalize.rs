@@ -0,0 +1,332 @@
+//! Input normalization ahead of validation.
+//!
+//! Each base character and its combining marks are canonicalized to NFC as one cluster, so `Γ̇`
+//! reads the same whichever form it arrived in. Latin capitals drawn identically to Greek ones
+//! (`E`/`Ε`, `T`/`Τ`, `P`/`Ρ`, `X`/`Χ`) are confusables when they stand alone and are flagged.
+//! Letters inside Latin words are left alone.
+//!
+//! Explicit Phipe input may also use the ASCII spellings from the catalog, which
+//! [`normalize_aliases`] reads as their glyphs: `Phi` or `\Phi` for `Φ`, `Gamma.dot` for `Γ̇`,
+//! `->` for `→`, and `^n` for the `ⁿ` modifier; it also reads a confusable as the Greek operator
+//! when the catalog has one. [`to_ascii`] writes them back. Plain [`normalize`] leaves both
+//! alone, so prose such as "Phi Delta Theta" or "vitamin E" stays prose.
+
+use ri1_core::constraints::{Edit, ResonanceEvent, SourceSpan, Suggestion};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use super::ast::Span;
+use super::rules::{ValidatorConfig, CONFUSABLE_GLYPH};
+use crate::catalog::Catalog;
+
+const CONFUSABLES: [(char, char); 4] = [('E', 'Ε'), ('T', 'Τ'), ('P', 'Ρ'), ('X', 'Χ')];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Confusable {
+    pub found: char,
+    pub greek: char,
+    /// Replaced by `greek` in the normalized text; only when reading aliases.
+    pub mapped: bool,
+    /// Location in the normalized text.
+    pub span: Span,
+}
+
+/// A changed cluster: byte range in the input and in the normalized text.
+#[derive(Debug, Clone, Copy)]
+struct Rewrite {
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Normalized {
+    pub text: String,
+    pub confusables: Vec<Confusable>,
+    rewrites: Vec<Rewrite>,
+    aliased: bool,
+}
+
+impl Normalized {
+    pub fn changed(&self) -> bool {
+        !self.rewrites.is_empty()
+    }
+
+    /// Whether an ASCII alias was read as its glyph.
+    pub fn aliased(&self) -> bool {
+        self.aliased
+    }
+
+    /// Input offset of byte `at` of the normalized text; offsets inside a rewritten cluster
+    /// resolve to its start.
+    pub fn original_offset(&self, at: usize) -> usize {
+        let mut shift = 0isize;
+        for r in &self.rewrites {
+            if at < r.to.0 {
+                break;
+            }
+            if at < r.to.1 {
+                return r.from.0;
+            }
+            if at == r.to.1 {
+                return r.from.1;
+            }
+            shift += (r.from.1 - r.from.0) as isize - (r.to.1 - r.to.0) as isize;
+        }
+        (at as isize + shift) as usize
+    }
+
+    /// `span` of the normalized text, located in `original`.
+    pub fn original_span(&self, span: SourceSpan, original: &str) -> SourceSpan {
+        SourceSpan::locate(
+            original,
+            self.original_offset(span.start),
+            self.original_offset(span.end),
+        )
+    }
+}
+
+/// NFC clusters only; confusables are flagged and ASCII aliases are left as written.
+pub fn normalize(input: &str, catalog: &Catalog) -> Normalized {
+    normalize_with(input, catalog, false)
+}
+
+/// [`normalize`], also reading ASCII aliases and confusables as their glyphs.
+pub fn normalize_aliases(input: &str, catalog: &Catalog) -> Normalized {
+    normalize_with(input, catalog, true)
+}
+
+fn normalize_with(input: &str, catalog: &Catalog, aliases: bool) -> Normalized {
+    let mut out = Normalized {
+        text: String::with_capacity(input.len()),
+        confusables: Vec::new(),
+        rewrites: Vec::new(),
+        aliased: false,
+    };
+    let latin_word = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    while start < input.len() {
+        let rest = &input[start..];
+        let at = out.text.len();
+        let (len, rewritten) = match alias(rest, prev, catalog).filter(|_| aliases) {
+            Some(found) => {
+                out.aliased = true;
+                found
+            }
+            None => {
+                let base = rest.chars().next().unwrap_or_default();
+                let marks: usize = rest[base.len_utf8()..]
+                    .chars()
+                    .take_while(|c| is_combining_mark(*c))
+                    .map(char::len_utf8)
+                    .sum();
+                let len = base.len_utf8() + marks;
+                let next = rest[len..].chars().next();
+                let confusable = CONFUSABLES.iter().find(|(l, _)| marks == 0 && *l == base);
+                let rewritten = match confusable {
+                    Some(&(found, greek)) if !latin_word(prev) && !latin_word(next) => {
+                        let mapped =
+                            aliases && catalog.operator(greek.encode_utf8(&mut [0; 4])).is_some();
+                        let glyph = if mapped { greek } else { found };
+                        out.confusables.push(Confusable {
+                            found,
+                            greek,
+                            mapped,
+                            span: Span::new(at, at + glyph.len_utf8()),
+                        });
+                        glyph.to_string()
+                    }
+                    _ => rest[..len].nfc().collect(),
+                };
+                (len, rewritten)
+            }
+        };
+        let cluster = &rest[..len];
+        if rewritten != cluster {
+            out.rewrites
+                .push(Rewrite { from: (start, start + len), to: (at, at + rewritten.len()) });
+        }
+        out.text.push_str(&rewritten);
+        prev = cluster.chars().last();
+        start += len;
+    }
+    out
+}
+
+/// An ASCII spelling at the start of `rest` (a conditional such as `->`, a modifier such as
+/// `^n`, or an operator name such as `Phi`, `\Phi` or `Gamma.dot` standing as its own word),
+/// with its length and glyph.
+fn alias(rest: &str, prev: Option<char>, catalog: &Catalog) -> Option<(usize, String)> {
+    let cond = catalog
+        .conditionals
+        .iter()
+        .find_map(|c| c.ascii.as_deref().filter(|a| rest.starts_with(a)).map(|a| (a, &c.symbol)));
+    if let Some((ascii, glyph)) = cond {
+        return Some((ascii.len(), glyph.clone()));
+    }
+    if let Some(sup) = rest.strip_prefix('^').and_then(|r| r.chars().next()).and_then(superscript) {
+        return Some((2, sup.to_string()));
+    }
+    if prev.is_some_and(|c| c.is_ascii_alphanumeric() || c == '\\' || c == '.') {
+        return None;
+    }
+    let name = rest.strip_prefix('\\').unwrap_or(rest);
+    let run = name.find(|c: char| !(c.is_ascii_alphabetic() || c == '.')).unwrap_or(name.len());
+    let word = name[..run].trim_end_matches('.');
+    if name[word.len()..].starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let glyph = catalog.glyph_for_ascii(word)?;
+    Some((rest.len() - name.len() + word.len(), glyph.to_string()))
+}
+
+const SUPERSCRIPTS: [(char, char); 12] = [
+    ('n', 'ⁿ'),
+    ('i', 'ⁱ'),
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+];
+
+fn superscript(c: char) -> Option<char> {
+    SUPERSCRIPTS.iter().find(|(a, _)| *a == c).map(|(_, s)| *s)
+}
+
+/// [`normalize_aliases`]d text, with every ASCII alias spelled as its glyph.
+pub fn to_glyphs(input: &str, catalog: &Catalog) -> String {
+    normalize_aliases(input, catalog).text
+}
+
+/// Spells operators, conditionals and superscript modifiers in `input` with their ASCII names,
+/// separating names that would otherwise run together (`ΕΔ` becomes `Epsilon Delta`).
+/// [`to_glyphs`] reverses it.
+pub fn to_ascii(input: &str, catalog: &Catalog) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.char_indices().peekable();
+    let mut after_name = false;
+    while let Some((start, base)) = chars.next() {
+        let mut end = start + base.len_utf8();
+        while let Some(&(i, c)) = chars.peek().filter(|(_, c)| is_combining_mark(*c)) {
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        let cluster = &input[start..end];
+        let sup = SUPERSCRIPTS.iter().find(|(_, s)| *s == base).map(|(a, _)| format!("^{}", a));
+        let (piece, name) = match (catalog.ascii_for(cluster), sup) {
+            (Some(a), _) => (a.to_string(), a.starts_with(|c: char| c.is_ascii_alphabetic())),
+            (None, Some(s)) => (s, false),
+            (None, None) => (cluster.to_string(), false),
+        };
+        let joins = out.ends_with(|c: char| c.is_ascii_alphanumeric())
+            && piece.starts_with(|c: char| c.is_ascii_alphanumeric());
+        if joins && (name || after_name) {
+            out.push(' ');
+        }
+        out.push_str(&piece);
+        after_name = name;
+    }
+    out
+}
+
+/// Notices for the confusables in `norm`, located in the normalized text. Flagged ones suggest
+/// typing the Greek letter.
+pub fn confusable_notices(norm: &Normalized, cfg: &ValidatorConfig) -> Vec<ResonanceEvent> {
+    norm.confusables
+        .iter()
+        .filter_map(|c| {
+            let codepoints = format!("U+{:04X} → U+{:04X}", c.found as u32, c.greek as u32);
+            let span = c.span.locate(&norm.text);
+            if c.mapped {
+                let msg =
+                    format!("Latin '{}' read as Greek '{}' ({})", c.found, c.greek, codepoints);
+                return cfg.emit(&CONFUSABLE_GLYPH, msg, Some(&c.greek.to_string()), Some(span));
+            }
+            let msg = format!(
+                "Latin '{}' looks like Greek '{}' ({}) and is read as Latin",
+                c.found, c.greek, codepoints
+            );
+            let fix = Suggestion {
+                message: format!("type Greek '{}'", c.greek),
+                edits: vec![Edit { span, replacement: c.greek.to_string() }],
+                safe: false,
+            };
+            cfg.emit(&CONFUSABLE_GLYPH, msg, Some(&c.found.to_string()), Some(span))
+                .map(|e| e.with_suggestion(fix))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn norm(src: &str) -> Normalized {
+        normalize(src, &Catalog::builtin())
+    }
+
+    #[test]
+    fn maps_lone_confusables_to_operators() {
+        let n = normalize_aliases("EΔΦ → Ω", &Catalog::builtin());
+        assert_eq!(n.text, "ΕΔΦ → Ω");
+        assert_eq!(
+            n.confusables[0],
+            Confusable { found: 'E', greek: 'Ε', mapped: true, span: Span::new(0, 2) }
+        );
+        // Χ is no operator: flagged, left as typed.
+        let n = normalize_aliases("Ψ X", &Catalog::builtin());
+        assert_eq!(n.text, "Ψ X");
+        assert!(!n.confusables[0].mapped);
+        // Without alias input confusables are only flagged.
+        let n = norm("vitamin E, P value");
+        assert_eq!(n.text, "vitamin E, P value");
+        assert!(n.confusables.iter().all(|c| !c.mapped) && n.confusables.len() == 2);
+        // Letters of Latin words are not confusables.
+        assert!(norm("TEXT: Δ").confusables.is_empty() && !norm("TEXT: Δ").changed());
+    }
+
+    #[test]
+    fn composes_clusters_and_maps_offsets_back() {
+        let n = normalize_aliases("E\u{0301} P Δ", &Catalog::builtin());
+        assert_eq!(n.text, "\u{00C9} Ρ Δ");
+        assert_eq!(n.confusables.len(), 1);
+        let delta = n.text.find('Δ').unwrap();
+        assert_eq!(n.original_offset(delta), "E\u{0301} P Δ".find('Δ').unwrap());
+        let rho = n.text.find('Ρ').unwrap();
+        assert_eq!((n.original_offset(rho), n.original_offset(rho + 2)), (4, 5));
+        assert_eq!(norm("Γ\u{0307}").text, "Γ\u{0307}");
+    }
+
+    #[test]
+    fn ascii_aliases_become_glyphs() {
+        let n = normalize_aliases("Gamma.dot^n : \\Sigma(Psi) -> Omega.", &Catalog::builtin());
+        assert_eq!(n.text, "Γ̇ⁿ : Σ(Ψ) → Ω.");
+        let omega = n.text.find('Ω').unwrap();
+        assert_eq!(n.original_offset(omega), "Gamma.dot^n : \\Sigma(Psi) -> ".len());
+        // Names inside words or unknown names are text.
+        assert_eq!(
+            to_glyphs("Alpha Phis TEXT: Phi2", &Catalog::builtin()),
+            "Alpha Phis TEXT: Phi2"
+        );
+        // Only on request.
+        assert!(!norm("Phi Delta Theta -> Omega").changed());
+        assert!(!norm("E Δ").aliased() && normalize_aliases("Phi", &Catalog::builtin()).aliased());
+    }
+
+    #[test]
+    fn ascii_round_trips() {
+        let c = Catalog::builtin();
+        for src in ["Ε Δ Φ → Ω", "Γ̇ⁿ : Σ(Ψ, Γ) = Ω", "[Ψ → Δ] | λ", "ΕΔΦ"] {
+            let ascii = to_ascii(src, &c);
+            assert!(ascii.is_ascii(), "{}", ascii);
+            assert_eq!(to_glyphs(&ascii, &c).replace(' ', ""), src.replace(' ', ""));
+        }
+        assert_eq!(to_ascii("ΕΔΦ → Γ̇ⁿ", &c), "Epsilon Delta Phi -> Gamma.dot^n");
+    }
+}
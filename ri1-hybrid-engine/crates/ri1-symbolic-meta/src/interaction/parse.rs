@@ -0,0 +1,334 @@
+//! Recursive-descent parser producing an [`Ast`] from Phipe expression text.
+//!
+//! Grammar (lowest to highest binding):
+//!
+//! ```text
+//! expr     := sequence (conditional sequence)*      ; precedence climbing, left-assoc
+//!             conditional: '=' < '→' < '|' < '/' < ':' < '+'
+//! sequence := primary+                              ; juxtaposition, e.g. ΕΔΦ or Δ Λ Ψ
+//! primary  := symbol '(' expr (',' expr)* ')'       ; application, no space before '('
+//!           | symbol | '[' expr? ']' | '(' expr ')' | text
+//! symbol   := greek-letter combining-mark* superscript*
+//! ```
+//!
+//! The parser never fails: unbalanced brackets, stray separators and missing operands
+//! are recorded as [`ParseError`]s and the tree is recovered around them.
+
+use super::ast::{
+    Ast, Conditional, Expr, ExprKind, Modifier, ParseError, ParseErrorKind, Span, Symbol,
+};
+use super::tokenize::{tokenize, Token, TokenKind};
+
+/// Greek letters are operator glyphs; anything else lexed as a symbol is plain text.
+pub fn is_operator_glyph(ch: char) -> bool {
+    ('\u{0370}'..='\u{03FF}').contains(&ch) && ch.is_alphabetic()
+}
+
+pub fn parse(input: &str) -> Ast {
+    let mut p = Parser { src: input, tokens: tokenize(input), pos: 0, errors: Vec::new() };
+    let (root, _) = p.body_until(None);
+    Ast { root, errors: p.errors }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    errors: Vec<ParseError>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|t| &t.kind)
+    }
+
+    fn bump(&mut self) -> Token {
+        let t = self.tokens[self.pos].clone();
+        self.pos += 1;
+        t
+    }
+
+    /// Start offset of the next token, or end of input.
+    fn here(&self) -> usize {
+        self.peek().map(|t| t.span.start).unwrap_or(self.src.len())
+    }
+
+    /// True when the next token starts exactly where `prev` ended (no whitespace between).
+    fn next_is_adjacent(&self, prev: Span) -> bool {
+        self.peek().map(|t| t.span.start == prev.end).unwrap_or(false)
+    }
+
+    fn error(&mut self, kind: ParseErrorKind, message: impl Into<String>, span: Span) {
+        self.errors.push(ParseError { kind, message: message.into(), span });
+    }
+
+    fn conditional(kind: &TokenKind) -> Option<Conditional> {
+        match kind {
+            TokenKind::Arrow => Some(Conditional::Flow),
+            TokenKind::Plus => Some(Conditional::Simultaneity),
+            TokenKind::Colon => Some(Conditional::Interaction),
+            TokenKind::Slash => Some(Conditional::Disruption),
+            TokenKind::Pipe => Some(Conditional::Orthogonality),
+            TokenKind::Equals => Some(Conditional::Stabilization),
+            _ => None,
+        }
+    }
+
+    /// Parses expressions until `close` or end of input, skipping stray closers. Returns the
+    /// end offset of `close` when it was found and consumed.
+    fn body_until(&mut self, close: Option<TokenKind>) -> (Option<Expr>, Option<usize>) {
+        let mut items = Vec::new();
+        loop {
+            match self.peek_kind() {
+                None => break,
+                Some(k) if Some(k) == close.as_ref() => {
+                    let end = self.bump().span.end;
+                    return (Self::join(items), Some(end));
+                }
+                Some(TokenKind::RBracket | TokenKind::RParen | TokenKind::Comma) => {
+                    let t = self.bump();
+                    let what = match t.kind {
+                        TokenKind::RBracket => "unmatched ']'",
+                        TokenKind::RParen => "unmatched ')'",
+                        _ => "',' outside of an application",
+                    };
+                    self.error(ParseErrorKind::Unmatched, what, t.span);
+                }
+                Some(_) => items.push(self.expr(0)),
+            }
+        }
+        (Self::join(items), None)
+    }
+
+    fn join(mut items: Vec<Expr>) -> Option<Expr> {
+        match items.len() {
+            0 => None,
+            1 => items.pop(),
+            _ => {
+                let span = items[0].span.to(items[items.len() - 1].span);
+                Some(Expr::new(ExprKind::Sequence(items), span))
+            }
+        }
+    }
+
+    fn expr(&mut self, min_prec: u8) -> Expr {
+        let mut lhs = self.sequence();
+        while let Some(op) = self.peek_kind().and_then(Self::conditional) {
+            let prec = op.precedence();
+            if prec < min_prec {
+                break;
+            }
+            let op_span = self.bump().span;
+            let rhs = self.expr(prec + 1);
+            let span = lhs.span.to(op_span).to(rhs.span);
+            lhs = Expr::new(
+                ExprKind::Binary { op, op_span, lhs: Box::new(lhs), rhs: Box::new(rhs) },
+                span,
+            );
+        }
+        lhs
+    }
+
+    fn sequence(&mut self) -> Expr {
+        let mut items = Vec::new();
+        while let Some(k) = self.peek_kind() {
+            if k.is_conditional()
+                || matches!(k, TokenKind::RBracket | TokenKind::RParen | TokenKind::Comma)
+            {
+                break;
+            }
+            items.push(self.primary());
+        }
+        match Self::join(items) {
+            Some(e) => e,
+            None => {
+                let span = Span::point(self.here());
+                self.error(ParseErrorKind::MissingOperand, "expected operand", span);
+                Expr::new(ExprKind::Missing, span)
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Expr {
+        let open = self.bump();
+        match open.kind {
+            TokenKind::LBracket => {
+                let (body, end) = self.body_until(Some(TokenKind::RBracket));
+                let end = self.closed_at(&open, end, "unclosed '[' loop container");
+                Expr::new(ExprKind::Loop(body.map(Box::new)), open.span.to(Span::point(end)))
+            }
+            TokenKind::LParen => {
+                let (body, end) = self.body_until(Some(TokenKind::RParen));
+                let end = self.closed_at(&open, end, "unclosed '('");
+                let span = open.span.to(Span::point(end));
+                let body = body.unwrap_or_else(|| {
+                    self.error(ParseErrorKind::MissingOperand, "empty group", span);
+                    Expr::new(ExprKind::Missing, Span::point(open.span.end))
+                });
+                Expr::new(ExprKind::Group(Box::new(body)), span)
+            }
+            TokenKind::Symbol(c) if is_operator_glyph(c) => {
+                let sym = self.symbol_tail(c, open.span);
+                if matches!(self.peek_kind(), Some(TokenKind::LParen))
+                    && self.next_is_adjacent(sym.span)
+                {
+                    self.application(sym)
+                } else {
+                    let span = sym.span;
+                    Expr::new(ExprKind::Symbol(sym), span)
+                }
+            }
+            _ => {
+                // Text run: adjacent non-operator symbols, marks and superscripts.
+                let mut span = open.span;
+                while self.next_is_adjacent(span) {
+                    match self.peek_kind() {
+                        Some(TokenKind::Symbol(c)) if !is_operator_glyph(*c) => {}
+                        Some(TokenKind::Combining(_) | TokenKind::Superscript(_)) => {}
+                        _ => break,
+                    }
+                    span = span.to(self.bump().span);
+                }
+                Expr::new(ExprKind::Text(self.src[span.start..span.end].to_string()), span)
+            }
+        }
+    }
+
+    /// End offset of a container opened by `open`, or end of input with an error when the
+    /// closer was never found.
+    fn closed_at(&mut self, open: &Token, end: Option<usize>, unclosed: &str) -> usize {
+        end.unwrap_or_else(|| {
+            self.error(ParseErrorKind::Unclosed, unclosed, open.span);
+            self.src.len()
+        })
+    }
+
+    fn symbol_tail(&mut self, base: char, span: Span) -> Symbol {
+        let marks = self.src[span.start + base.len_utf8()..span.end].to_string();
+        let mut sym = Symbol { base, marks, modifiers: Vec::new(), span };
+        while self.next_is_adjacent(sym.span) {
+            match self.peek_kind() {
+                Some(TokenKind::Combining(m)) if sym.modifiers.is_empty() => {
+                    sym.marks.push(*m);
+                }
+                Some(TokenKind::Superscript(g)) => {
+                    let glyph = *g;
+                    let span = self.peek().map(|t| t.span).unwrap_or(sym.span);
+                    sym.modifiers.push(Modifier { glyph, span });
+                }
+                _ => break,
+            }
+            sym.span = sym.span.to(self.bump().span);
+        }
+        sym
+    }
+
+    fn application(&mut self, head: Symbol) -> Expr {
+        let open = self.bump();
+        let mut args = Vec::new();
+        let mut end = None;
+        if matches!(self.peek_kind(), Some(TokenKind::RParen)) {
+            end = Some(self.bump().span.end);
+        }
+        while end.is_none() {
+            args.push(self.expr(0));
+            match self.peek_kind() {
+                Some(TokenKind::Comma) => {
+                    self.bump();
+                }
+                Some(TokenKind::RParen) => end = Some(self.bump().span.end),
+                Some(_) => {
+                    let t = self.bump();
+                    self.error(
+                        ParseErrorKind::Unmatched,
+                        "unexpected ']' inside application",
+                        t.span,
+                    );
+                }
+                None => {
+                    self.error(ParseErrorKind::Unclosed, "unclosed '(' in application", open.span);
+                    end = Some(self.src.len());
+                }
+            }
+        }
+        let span = head.span.to(Span::point(end.unwrap_or(self.src.len())));
+        Expr::new(ExprKind::Apply { head, args }, span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(src: &str) -> Expr {
+        let ast = parse(src);
+        assert!(ast.errors.is_empty(), "unexpected errors for {:?}: {:?}", src, ast.errors);
+        ast.root.expect("root")
+    }
+
+    #[test]
+    fn conditionals_respect_precedence() {
+        // '=' binds loosest, so the whole flow is its left operand.
+        let e = root("Ψ : Φ → Γ = Ω");
+        let ExprKind::Binary { op, lhs, .. } = &e.kind else { panic!("{:?}", e) };
+        assert_eq!(*op, Conditional::Stabilization);
+        let ExprKind::Binary { op, lhs, .. } = &lhs.kind else { panic!() };
+        assert_eq!(*op, Conditional::Flow);
+        assert!(matches!(lhs.kind, ExprKind::Binary { op: Conditional::Interaction, .. }));
+    }
+
+    #[test]
+    fn juxtaposition_and_loops() {
+        let e = root("[ΔΨ] → Ξ");
+        let ExprKind::Binary { lhs, .. } = &e.kind else { panic!() };
+        let ExprKind::Loop(Some(body)) = &lhs.kind else { panic!("{:?}", lhs) };
+        let ExprKind::Sequence(items) = &body.kind else { panic!() };
+        assert_eq!(items.len(), 2);
+        assert_eq!(lhs.span, Span::new(0, "[ΔΨ]".len()));
+    }
+
+    #[test]
+    fn applications_and_modifiers() {
+        let e = root("Σ(Ψ, Γ) + ζ(Ψⁿ)");
+        let ExprKind::Binary { lhs, rhs, .. } = &e.kind else { panic!() };
+        let ExprKind::Apply { head, args } = &lhs.kind else { panic!() };
+        assert!(head.is("Σ"));
+        assert_eq!(args.len(), 2);
+        let ExprKind::Apply { args, .. } = &rhs.kind else { panic!() };
+        let ExprKind::Symbol(psi) = &args[0].kind else { panic!() };
+        assert_eq!(psi.modifiers.iter().map(|m| m.glyph).collect::<String>(), "ⁿ");
+    }
+
+    #[test]
+    fn combining_forms_are_one_symbol() {
+        let e = root("Γ̇ → Γ");
+        let ExprKind::Binary { lhs, .. } = &e.kind else { panic!() };
+        let ExprKind::Symbol(s) = &lhs.kind else { panic!() };
+        assert_eq!(s.glyph(), "Γ̇");
+        assert_eq!(s.span, Span::new(0, "Γ̇".len()));
+    }
+
+    #[test]
+    fn recovers_from_unbalanced_and_missing_operands() {
+        let ast = parse("Δ Λ Ψ Ω =");
+        assert_eq!(ast.errors.len(), 1);
+        assert_eq!(ast.errors[0].span, Span::point("Δ Λ Ψ Ω =".len()));
+
+        let ast = parse("[Δ → Ψ");
+        assert!(ast.errors.iter().any(|e| e.message.contains("unclosed '['")));
+        let ast = parse("Δ] Ψ");
+        assert!(ast.errors.iter().any(|e| e.message.contains("unmatched ']'")));
+        assert_eq!(ast.symbols().len(), 2);
+    }
+
+    #[test]
+    fn text_runs_are_not_operators() {
+        let ast = parse("TEXT: Φ + Σ");
+        assert_eq!(ast.symbols().len(), 2);
+        assert!(ast.contains_symbol("Φ"));
+    }
+}
@@ -1,4 +1,17 @@
-use ri1_core::constraints::{OperatorClass, ResonanceEvent};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use ri1_core::constraints::{
+    ConstraintResult, OperatorClass, ResonanceEvent, Severity, SourceSpan,
+};
+use serde::Deserialize;
+
+use crate::gates::{GateCheck, GateRegistry};
+
+use super::ast::{Ast, Span};
+use super::interpret::Execution;
+use super::tokenize::Token;
 
 #[derive(Debug, Clone)]
 pub struct RuleViolation {
@@ -6,23 +19,496 @@ pub struct RuleViolation {
     pub symbol: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct ValidatorConfig {}
+/// How a rule's findings are reported. `Hard` also fails a hard constraint, which blocks
+/// generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    Notice,
+    Violation,
+    #[serde(alias = "hard-block", alias = "block")]
+    Hard,
+}
+
+/// Per-rule override; omitted fields keep the rule's defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSetting {
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub severity: Option<RuleSeverity>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Default for RuleSetting {
+    fn default() -> Self {
+        Self { enabled: true, severity: None }
+    }
+}
+
+/// Per-gate-check override; omitted fields keep the check's defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GateCheckSetting {
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub severity: Option<Severity>,
+}
+
+impl Default for GateCheckSetting {
+    fn default() -> Self {
+        Self { enabled: true, severity: None }
+    }
+}
+
+/// Rule overrides keyed by rule code. Loaded from TOML such as
+///
+/// ```toml
+/// [rules.PHI004]
+/// enabled = false
+///
+/// [rules.post-equals-transform]
+/// severity = "hard"
+///
+/// [gates.pi_support]
+/// severity = "hard"
+/// ```
+///
+/// Rules may be named by code or by name; unknown rules are rejected. `[gates.*]` tables
+/// override gate checks by name: the built-in ones, or those of the registry given to
+/// [`ValidatorConfig::load_with`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidatorConfig {
+    rules: BTreeMap<&'static str, RuleSetting>,
+    gates: BTreeMap<&'static str, GateCheckSetting>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+    UnknownRule(String),
+    UnknownGateCheck(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "cannot read validator config: {}", e),
+            ConfigError::Parse(e) => write!(f, "invalid validator config: {}", e),
+            ConfigError::UnknownRule(r) => write!(f, "unknown interaction rule '{}'", r),
+            ConfigError::UnknownGateCheck(c) => write!(f, "unknown gate check '{}'", c),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    rules: BTreeMap<String, RuleSetting>,
+    #[serde(default)]
+    gates: BTreeMap<String, GateCheckSetting>,
+}
+
+impl ValidatorConfig {
+    /// Parses overrides for the built-in rules and gate checks.
+    pub fn from_toml_str(src: &str) -> Result<Self, ConfigError> {
+        Self::from_toml_str_with(src, &RuleRegistry::default(), GateRegistry::builtin_ref())
+    }
+
+    /// Parses overrides for the built-in rules and those registered in `registry`, and for
+    /// the checks bound in `gates`.
+    pub fn from_toml_str_with(
+        src: &str,
+        registry: &RuleRegistry,
+        gates: &GateRegistry,
+    ) -> Result<Self, ConfigError> {
+        let file: ConfigFile = toml::from_str(src).map_err(ConfigError::Parse)?;
+        let mut cfg = Self::default();
+        for (key, setting) in file.rules {
+            let rule = registry
+                .infos()
+                .find(|r| r.code == key || r.name == key)
+                .ok_or(ConfigError::UnknownRule(key))?;
+            cfg.rules.insert(rule.code, setting);
+        }
+        for (key, setting) in file.gates {
+            let check =
+                gates.checks().find(|c| c.name == key).ok_or(ConfigError::UnknownGateCheck(key))?;
+            cfg.gates.insert(check.name, setting);
+        }
+        Ok(cfg)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::load_with(path, &RuleRegistry::default(), GateRegistry::builtin_ref())
+    }
+
+    /// Loads overrides for an engine's rules and gates: `load_with(path, engine.rules(), engine.gates())`.
+    pub fn load_with(
+        path: impl AsRef<Path>,
+        registry: &RuleRegistry,
+        gates: &GateRegistry,
+    ) -> Result<Self, ConfigError> {
+        let src = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::from_toml_str_with(&src, registry, gates)
+    }
+
+    pub fn set(&mut self, rule: &RuleInfo, setting: RuleSetting) {
+        self.rules.insert(rule.code, setting);
+    }
+
+    pub fn is_enabled(&self, rule: &RuleInfo) -> bool {
+        self.rules.get(rule.code).is_none_or(|s| s.enabled)
+    }
+
+    pub fn severity(&self, rule: &RuleInfo) -> RuleSeverity {
+        self.rules.get(rule.code).and_then(|s| s.severity).unwrap_or(rule.severity)
+    }
+
+    pub fn set_gate_check(&mut self, check: &GateCheck, setting: GateCheckSetting) {
+        self.gates.insert(check.name, setting);
+    }
+
+    pub fn gate_check_enabled(&self, check: &GateCheck) -> bool {
+        self.gates.get(check.name).is_none_or(|s| s.enabled)
+    }
+
+    pub fn gate_check_severity(&self, check: &GateCheck) -> Severity {
+        self.gates.get(check.name).and_then(|s| s.severity).unwrap_or(check.severity)
+    }
+
+    /// Builds the event for a finding of `rule`, or `None` when the rule is disabled.
+    pub fn emit(
+        &self,
+        rule: &RuleInfo,
+        message: impl Into<String>,
+        symbol: Option<&str>,
+        span: Option<SourceSpan>,
+    ) -> Option<ResonanceEvent> {
+        if !self.is_enabled(rule) {
+            return None;
+        }
+        Some(match self.severity(rule) {
+            RuleSeverity::Notice => notice(rule, message, symbol, span),
+            RuleSeverity::Violation | RuleSeverity::Hard => violation(rule, message, symbol, span),
+        })
+    }
+
+    /// Failed hard constraints for events raised by rules configured as `Hard`.
+    pub fn blocking(
+        &self,
+        events: &[ResonanceEvent],
+        registry: &RuleRegistry,
+    ) -> Vec<ConstraintResult> {
+        events
+            .iter()
+            .filter_map(|e| Some((e, registry.info(e.code.as_deref()?)?)))
+            .filter(|(_, rule)| self.severity(rule) == RuleSeverity::Hard)
+            .map(|(e, rule)| ConstraintResult {
+                passed: false,
+                severity: Severity::Hard,
+                name: rule.code,
+                message: Some(e.message.clone()),
+                span: e.span,
+            })
+            .collect()
+    }
+}
+
+/// Stable identity of an interaction rule, surfaced as `ResonanceEvent::code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleInfo {
+    pub code: &'static str,
+    pub name: &'static str,
+    /// Severity when no [`ValidatorConfig`] override applies.
+    pub severity: RuleSeverity,
+    pub help: &'static str,
+}
+
+pub const BRACKET_BALANCE: RuleInfo = RuleInfo {
+    code: "PHI001",
+    name: "bracket-balance",
+    severity: RuleSeverity::Violation,
+    help: "every '[' loop container needs a matching ']'",
+};
+pub const POST_EQUALS_TRANSFORM: RuleInfo = RuleInfo {
+    code: "PHI002",
+    name: "post-equals-transform",
+    severity: RuleSeverity::Violation,
+    help: "move Δ/Ξ/Π before '='; stabilization must be the last step",
+};
+pub const PIPE_COLON_SCOPE: RuleInfo = RuleInfo {
+    code: "PHI003",
+    name: "pipe-colon-scope",
+    severity: RuleSeverity::Notice,
+    help: "'|' declares the fields non-interacting; put the ':' interaction in its own '[]' scope",
+};
+pub const FLOW_CYCLE: RuleInfo = RuleInfo {
+    code: "PHI004",
+    name: "flow-cycle",
+    severity: RuleSeverity::Notice,
+    help: "wrap intentional feedback in a '[]' loop container",
+};
+pub const COLON_TERMINAL: RuleInfo = RuleInfo {
+    code: "PHI005",
+    name: "colon-terminal",
+    severity: RuleSeverity::Notice,
+    help: "add the operand that ':' interacts with",
+};
+pub const EQUALS_TERMINAL: RuleInfo = RuleInfo {
+    code: "PHI006",
+    name: "equals-terminal",
+    severity: RuleSeverity::Violation,
+    help: "give '=' a finalized right-hand form, e.g. `Ψ = Ω`",
+};
+pub const COLON_OPERANDS: RuleInfo = RuleInfo {
+    code: "PHI007",
+    name: "colon-operands",
+    severity: RuleSeverity::Violation,
+    help: "':' joins two operands, e.g. `Ψ : Φ`",
+};
+pub const POST_OMEGA_TRANSFORM: RuleInfo = RuleInfo {
+    code: "PHI008",
+    name: "post-omega-transform",
+    severity: RuleSeverity::Violation,
+    help: "move Δ/Ξ/Π before Ω; closure must be terminal",
+};
+pub const POST_COLLAPSE_ACTION: RuleInfo = RuleInfo {
+    code: "PHI009",
+    name: "post-collapse-action",
+    severity: RuleSeverity::Violation,
+    help: "Δ collapse is irreversible; keep the fields apart with Φ or Σ before fusing them",
+};
+pub const CONFUSABLE_GLYPH: RuleInfo = RuleInfo {
+    code: "PHI010",
+    name: "confusable-glyph",
+    severity: RuleSeverity::Notice,
+    help: "type the Greek letter; a lone Latin look-alike is read as the operator it resembles",
+};
+pub const UNKNOWN_FIELD_PHASE: RuleInfo = RuleInfo {
+    code: "PHI011",
+    name: "unknown-field-phase",
+    severity: RuleSeverity::Violation,
+    help: "the field context names a phase the engine does not know",
+};
+pub const MALFORMED_FIELD_ID: RuleInfo = RuleInfo {
+    code: "PHI012",
+    name: "malformed-field-id",
+    severity: RuleSeverity::Violation,
+    help: "field ids are 1-64 ASCII letters, digits, '.', '_', ':' or '-', starting with a letter or digit",
+};
+pub const PHASE_OPERATOR: RuleInfo = RuleInfo {
+    code: "PHI013",
+    name: "phase-operator",
+    severity: RuleSeverity::Violation,
+    help: "the field's phase does not permit this operator; advance the field first",
+};
+pub const PHASE_REQUIRED_OPERATOR: RuleInfo = RuleInfo {
+    code: "PHI014",
+    name: "phase-required-operator",
+    severity: RuleSeverity::Violation,
+    help: "expressions in this phase must use the operator",
+};
+pub const PHASE_TRANSITION: RuleInfo = RuleInfo {
+    code: "PHI015",
+    name: "phase-transition",
+    severity: RuleSeverity::Notice,
+    help: "the expression uses the operators that advance the field to its next phase",
+};
+
+/// All built-in interaction rules in check order.
+pub const RULES: &[RuleInfo] = &[
+    BRACKET_BALANCE,
+    POST_EQUALS_TRANSFORM,
+    PIPE_COLON_SCOPE,
+    FLOW_CYCLE,
+    COLON_TERMINAL,
+    EQUALS_TERMINAL,
+    COLON_OPERANDS,
+    POST_OMEGA_TRANSFORM,
+    POST_COLLAPSE_ACTION,
+    CONFUSABLE_GLYPH,
+    UNKNOWN_FIELD_PHASE,
+    MALFORMED_FIELD_ID,
+    PHASE_OPERATOR,
+    PHASE_REQUIRED_OPERATOR,
+    PHASE_TRANSITION,
+];
+
+pub fn rule_info(code: &str) -> Option<&'static RuleInfo> {
+    RULES.iter().find(|r| r.code == code)
+}
+
+/// What a rule gets to inspect; parsed and executed once per validation.
+pub struct RuleContext<'a> {
+    pub content: &'a str,
+    pub tokens: &'a [Token],
+    pub ast: &'a Ast,
+    /// Execution through the engine's gates.
+    pub execution: &'a Execution,
+    pub gates: &'a GateRegistry,
+}
+
+impl RuleContext<'_> {
+    pub fn locate(&self, span: Span) -> SourceSpan {
+        span.locate(self.content)
+    }
+}
+
+/// One finding of a rule, reported with the rule's code and configured severity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub message: String,
+    pub symbol: Option<String>,
+    pub span: Option<Span>,
+}
+
+impl Finding {
+    pub fn new(message: impl Into<String>, symbol: Option<&str>, span: Option<Span>) -> Self {
+        Self { message: message.into(), symbol: symbol.map(String::from), span }
+    }
+}
+
+/// A structural check run alongside the built-in rules. Its findings become
+/// `InteractionNotice`/`InteractionViolation` events and count towards `interaction_summary`.
+pub trait InteractionRule: Send + Sync {
+    /// Code must be unique; it is how [`ValidatorConfig`] addresses the rule.
+    fn info(&self) -> &RuleInfo;
+    fn check(&self, cx: &RuleContext<'_>) -> Vec<Finding>;
+}
+
+/// Rules registered on top of the built-in [`RULES`].
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: Vec<Box<dyn InteractionRule>>,
+}
+
+/// A rule whose code or name is already taken, by a built-in or an earlier registration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    Duplicate { code: &'static str, name: &'static str },
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::Duplicate { code, name } => {
+                write!(f, "interaction rule {} ({}) is already registered", code, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+impl RuleRegistry {
+    /// Fails if the rule's code or name is already taken.
+    pub fn register<R: InteractionRule + 'static>(&mut self, rule: R) -> Result<(), RuleError> {
+        let info = *rule.info();
+        if self.infos().any(|r| r.code == info.code || r.name == info.name) {
+            return Err(RuleError::Duplicate { code: info.code, name: info.name });
+        }
+        self.rules.push(Box::new(rule));
+        Ok(())
+    }
+
+    /// Registered rules, in registration order.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn InteractionRule> {
+        self.rules.iter().map(|r| r.as_ref())
+    }
+
+    /// Built-in rules followed by registered ones.
+    pub fn infos(&self) -> impl Iterator<Item = &RuleInfo> {
+        RULES.iter().chain(self.rules.iter().map(|r| r.info()))
+    }
+
+    pub fn info(&self, code: &str) -> Option<&RuleInfo> {
+        self.infos().find(|r| r.code == code)
+    }
+}
 
-pub fn violation(message: impl Into<String>, symbol: Option<&str>) -> ResonanceEvent {
+pub fn violation(
+    rule: &RuleInfo,
+    message: impl Into<String>,
+    symbol: Option<&str>,
+    span: Option<SourceSpan>,
+) -> ResonanceEvent {
     ResonanceEvent {
         operator: OperatorClass::InteractionViolation,
         message: message.into(),
         section_ref: None,
         symbol: symbol.map(|s| s.to_string()),
+        code: Some(rule.code.to_string()),
+        span,
+        suggestions: Vec::new(),
     }
 }
 
-pub fn notice(message: impl Into<String>, symbol: Option<&str>) -> ResonanceEvent {
+pub fn notice(
+    rule: &RuleInfo,
+    message: impl Into<String>,
+    symbol: Option<&str>,
+    span: Option<SourceSpan>,
+) -> ResonanceEvent {
     ResonanceEvent {
         operator: OperatorClass::InteractionNotice,
         message: message.into(),
         section_ref: None,
         symbol: symbol.map(|s| s.to_string()),
+        code: Some(rule.code.to_string()),
+        span,
+        suggestions: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_overrides_by_code_or_name() {
+        let cfg = ValidatorConfig::from_toml_str(
+            "[rules.PHI004]\nenabled = false\n\n[rules.post-equals-transform]\nseverity = \"hard\"\n",
+        )
+        .unwrap();
+        assert!(!cfg.is_enabled(&FLOW_CYCLE));
+        assert_eq!(cfg.severity(&POST_EQUALS_TRANSFORM), RuleSeverity::Hard);
+        assert_eq!(cfg.severity(&COLON_TERMINAL), RuleSeverity::Notice);
+        assert!(cfg.emit(&FLOW_CYCLE, "x", None, None).is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_rules_and_fields() {
+        assert!(
+            matches!(ValidatorConfig::from_toml_str("[rules.PHI999]\n"), Err(ConfigError::UnknownRule(r)) if r == "PHI999")
+        );
+        assert!(matches!(
+            ValidatorConfig::from_toml_str("[rules.PHI001]\nlevel = 1\n"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            ValidatorConfig::from_toml_str("[rules.PHI001]\nseverity = \"fatal\"\n"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn severity_override_changes_event_class() {
+        let mut cfg = ValidatorConfig::default();
+        cfg.set(&COLON_TERMINAL, RuleSetting { enabled: true, severity: Some(RuleSeverity::Hard) });
+        let e = cfg.emit(&COLON_TERMINAL, "msg", Some(":"), None).unwrap();
+        assert_eq!(e.operator, OperatorClass::InteractionViolation);
+        let blocks = cfg.blocking(&[e], &RuleRegistry::default());
+        assert_eq!(blocks.len(), 1);
+        assert_eq!((blocks[0].name, blocks[0].passed), ("PHI005", false));
     }
 }
@@ -1,6 +1,10 @@
+use super::ast::Span;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenKind {
-    Symbol(char),
+    Symbol(char),      // base glyph; the span also covers its combining marks
+    Combining(char),   // U+0300..U+036F not attached to a symbol
+    Superscript(char), // ⁿ, ¹, ² ...
     Arrow,       // →
     Plus,        // +
     Colon,       // :
@@ -8,18 +12,56 @@ pub enum TokenKind {
     Pipe,        // |
     LBracket,    // [
     RBracket,    // ]
+    LParen,      // (
+    RParen,      // )
+    Comma,       // ,
     Equals,      // =
 }
 
+impl TokenKind {
+    /// True for the seven conditionals that join operands (`[]` excluded).
+    pub fn is_conditional(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Arrow
+                | TokenKind::Plus
+                | TokenKind::Colon
+                | TokenKind::Slash
+                | TokenKind::Pipe
+                | TokenKind::Equals
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Token {
     pub kind: TokenKind,
-    pub idx: usize,
+    /// Byte range of the token in the input, suitable for slicing.
+    pub span: Span,
+}
+
+pub fn is_combining(ch: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&ch)
+}
+
+pub fn is_superscript(ch: char) -> bool {
+    matches!(ch, 'ⁿ' | 'ⁱ' | '⁰' | '¹' | '²' | '³' | '⁴'..='⁹')
 }
 
+/// Splits `input` into tokens carrying byte spans. Whitespace is dropped, and combining marks
+/// directly after a symbol join its token, so `Γ̇` is one token.
 pub fn tokenize(input: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    for (i, ch) in input.chars().enumerate() {
+    let mut tokens: Vec<Token> = Vec::new();
+    for (at, ch) in input.char_indices() {
+        if ch.is_whitespace() {
+            continue;
+        }
+        if let Some(last) = tokens.last_mut().filter(|t| is_combining(ch) && t.span.end == at) {
+            if let TokenKind::Symbol(_) = last.kind {
+                last.span.end = at + ch.len_utf8();
+                continue;
+            }
+        }
         let kind = match ch {
             '→' => TokenKind::Arrow,
             '+' => TokenKind::Plus,
@@ -28,10 +70,15 @@ pub fn tokenize(input: &str) -> Vec<Token> {
             '|' => TokenKind::Pipe,
             '[' => TokenKind::LBracket,
             ']' => TokenKind::RBracket,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            ',' => TokenKind::Comma,
             '=' => TokenKind::Equals,
+            c if is_combining(c) => TokenKind::Combining(c),
+            c if is_superscript(c) => TokenKind::Superscript(c),
             other => TokenKind::Symbol(other),
         };
-        tokens.push(Token { kind, idx: i });
+        tokens.push(Token { kind, span: Span::new(at, at + ch.len_utf8()) });
     }
     tokens
 }
@@ -1,40 +1,64 @@
-use ri1_core::constraints::ResonanceEvent;
+use ri1_core::constraints::{Edit, ResonanceEvent, Suggestion};
 
-use super::rules::{notice, violation, ValidatorConfig};
+use super::ast::Span;
+use super::rules::{
+    RuleContext, RuleRegistry, ValidatorConfig, BRACKET_BALANCE, COLON_OPERANDS, COLON_TERMINAL, EQUALS_TERMINAL,
+    FLOW_CYCLE, PIPE_COLON_SCOPE, POST_COLLAPSE_ACTION, POST_EQUALS_TRANSFORM, POST_OMEGA_TRANSFORM,
+};
+use super::flow::FlowGraph;
+use crate::gates::GateRegistry;
+use super::interpret::{execute_ast, SemanticErrorKind};
+use super::parse::parse;
 use super::tokenize::{tokenize, TokenKind};
 
-pub fn validate_interactions(content: &str, _cfg: &ValidatorConfig) -> Vec<ResonanceEvent> {
+pub fn validate_interactions(content: &str, cfg: &ValidatorConfig) -> Vec<ResonanceEvent> {
+    validate_with_rules(content, cfg, &RuleRegistry::default(), GateRegistry::builtin_ref())
+}
+
+/// Runs the built-in rules, then every rule in `registry`, in registration order. The
+/// expression is executed through `gates`.
+pub fn validate_with_rules(content: &str, cfg: &ValidatorConfig, registry: &RuleRegistry, gates: &GateRegistry) -> Vec<ResonanceEvent> {
     let mut events = Vec::new();
     let tokens = tokenize(content);
+    let at = |span: Span| Some(span.locate(content));
+    let edit = |span: Span, replacement: &str| Edit { span: span.locate(content), replacement: replacement.into() };
+    let fix = |message: &str, edits: Vec<Edit>, safe: bool| Suggestion { message: message.into(), edits, safe };
+    // Ordering rules (2, 7, 8) come from executing the expression over a field state.
+    let ast = parse(content);
+    let exec = execute_ast(&ast, content, gates);
+    let first = |kind: SemanticErrorKind| exec.errors.iter().find(|e| e.kind == kind);
 
-    // 1) Bracket balance
-    let mut bal = 0i32;
+    // 1) Bracket balance (open '[' spans are kept so an unclosed one can be pointed at)
+    let mut open: Vec<Span> = Vec::new();
+    let mut unmatched = None;
     for t in &tokens {
         match t.kind {
-            TokenKind::LBracket => bal += 1,
-            TokenKind::RBracket => bal -= 1,
-            _ => {}
-        }
-        if bal < 0 {
-            events.push(violation("Unmatched ']' detected", Some("[]")));
-            break;
-        }
-    }
-    if bal != 0 {
-        events.push(violation("Unbalanced '[]' loop container", Some("[]")));
-    }
-    // 2) '=' should be terminal (no Δ/Ξ/Π after first '=') — char-aware scan
-    let mut seen_eq = false;
-    for (_bi, ch) in content.char_indices() {
-        if !seen_eq {
-            if ch == '=' { seen_eq = true; }
-        } else {
-            if ch == 'Δ' || ch == 'Ξ' || ch == 'Π' {
-                events.push(violation("Post '=' transform detected (Δ/Ξ/Π). Stabilization should be terminal.", Some("=")));
+            TokenKind::LBracket => open.push(t.span),
+            TokenKind::RBracket if open.pop().is_none() => {
+                let remove = fix("remove the unmatched ']'", vec![edit(t.span, "")], true);
+                events.extend(cfg.emit(&BRACKET_BALANCE, "Unmatched ']' detected", Some("[]"), at(t.span)).map(|e| e.with_suggestion(remove)));
+                unmatched = Some(t.span);
                 break;
             }
+            _ => {}
         }
     }
+    if let Some(span) = open.last().copied().or(unmatched) {
+        let event = cfg.emit(&BRACKET_BALANCE, "Unbalanced '[]' loop container", Some("[]"), at(span));
+        // Unclosed loops are closed at the end of the expression, keeping everything inside.
+        let close = (!open.is_empty()).then(|| {
+            let end = Span::point(content.trim_end().len());
+            fix("close the loop at the end of the expression", vec![edit(end, &"]".repeat(open.len()))], true)
+        });
+        events.extend(event.map(|e| match close {
+            Some(s) => e.with_suggestion(s),
+            None => e,
+        }));
+    }
+    // 2) '=' should be terminal (no Δ/Ξ/Π once the field is stabilized)
+    if let Some(err) = first(SemanticErrorKind::AfterStabilization) {
+        events.extend(cfg.emit(&POST_EQUALS_TRANSFORM, "Post '=' transform detected (Δ/Ξ/Π). Stabilization should be terminal.", Some("="), at(err.span)));
+    }
 
     // 3) ':' contradictions with '|'
     if content.contains('|') && content.contains(':') {
@@ -46,65 +70,90 @@ pub fn validate_interactions(content: &str, _cfg: &ValidatorConfig) -> Vec<Reson
                 TokenKind::LBracket => bal += 1,
                 TokenKind::RBracket => bal -= 1,
                 TokenKind::Colon => seen_colon_levels.push(bal),
-                TokenKind::Pipe => {
-                    if seen_colon_levels.iter().any(|lvl| *lvl == bal) {
-                        events.push(notice("'|' orthogonality with ':' interaction at same scope", Some("|")));
-                        break;
-                    }
+                TokenKind::Pipe if seen_colon_levels.contains(&bal) => {
+                    events.extend(cfg.emit(&PIPE_COLON_SCOPE, "'|' orthogonality with ':' interaction at same scope", Some("|"), at(t.span)));
+                    break;
                 }
                 _ => {}
             }
         }
     }
 
-    // 4) Attempt to detect naive cycles with '→' in both directions
-    if content.contains('→') {
-        // Very simple heuristic: A → B and B → A both present
-        // We just look for the literal reversed arrow presence and log a notice.
-        if content.matches('→').count() > 1 {
-            events.push(notice("Multiple '→' arcs; ensure no causal cycles (use '[]' for loops)", Some("→")));
-        }
+    // 4) Causal cycles in the '→' flow graph that no '[]' loop container encloses
+    for cycle in FlowGraph::from_ast(&ast).cycles().into_iter().filter(|c| !c.enclosed) {
+        let msg = format!("Causal cycle {} outside a '[]' loop container", cycle.describe());
+        events.extend(cfg.emit(&FLOW_CYCLE, msg, Some("→"), cycle.arrows.last().and_then(|a| at(*a))));
     }
 
     // 5) ':' cannot be terminal
-    if content.trim_end().ends_with(':') {
-        events.push(notice(": at end of expression; interaction requires counterpart", Some(":")));
+    let last = tokens.last();
+    // A trailing separator is dropped together with the whitespace before it.
+    let drop_last = |what: &str| {
+        let start = tokens.len().checked_sub(2).map(|i| tokens[i].span.end).unwrap_or(0);
+        let end = last.map(|t| t.span.end).unwrap_or(start);
+        fix(what, vec![edit(Span::new(start, end), "")], true)
+    };
+    if let Some(t) = last.filter(|t| t.kind == TokenKind::Colon) {
+        let event = cfg.emit(&COLON_TERMINAL, ": at end of expression; interaction requires counterpart", Some(":"), at(t.span));
+        events.extend(event.map(|e| e.with_suggestion(drop_last("remove the trailing ':'"))));
     }
 
     // 5b) '=' cannot be terminal (requires finalized right-hand form)
-    if content.trim_end().ends_with('=') {
-        events.push(violation("=: at end of expression; stabilization requires finalized form", Some("=")));
+    if let Some(t) = last.filter(|t| t.kind == TokenKind::Equals) {
+        let event = cfg.emit(&EQUALS_TERMINAL, "=: at end of expression; stabilization requires finalized form", Some("="), at(t.span));
+        events.extend(event.map(|e| e.with_suggestion(drop_last("remove the trailing '='"))));
     }
 
     // 6) ':' operand adjacency (must have operands on both sides that are not operators)
     for (i, t) in tokens.iter().enumerate() {
         if let TokenKind::Colon = t.kind {
-            // find prev non-operator token
-            let prev_ok = (0..i).rev().find_map(|j| match tokens[j].kind {
-                TokenKind::Symbol(_) | TokenKind::RBracket => Some(true),
-                TokenKind::LBracket | TokenKind::Arrow | TokenKind::Plus | TokenKind::Colon | TokenKind::Slash | TokenKind::Pipe | TokenKind::Equals => Some(false),
-            }).unwrap_or(false);
-            // find next non-operator token
-            let next_ok = ((i+1)..tokens.len()).find_map(|j| match tokens[j].kind {
-                TokenKind::Symbol(_) | TokenKind::LBracket => Some(true),
-                TokenKind::RBracket | TokenKind::Arrow | TokenKind::Plus | TokenKind::Colon | TokenKind::Slash | TokenKind::Pipe | TokenKind::Equals => Some(false),
-            }).unwrap_or(false);
+            // previous token must close an operand (whitespace is not tokenized)
+            let prev_ok = i.checked_sub(1).map(|j| matches!(tokens[j].kind,
+                TokenKind::Symbol(_) | TokenKind::Combining(_) | TokenKind::Superscript(_) | TokenKind::RBracket | TokenKind::RParen
+            )).unwrap_or(false);
+            // next token must open an operand
+            let next_ok = tokens.get(i + 1).map(|t| matches!(t.kind,
+                TokenKind::Symbol(_) | TokenKind::LBracket | TokenKind::LParen
+            )).unwrap_or(false);
             if !(prev_ok && next_ok) {
-                events.push(violation(": requires operands on both sides", Some(":")));
+                events.extend(cfg.emit(&COLON_OPERANDS, ": requires operands on both sides", Some(":"), at(t.span)));
             }
         }
     }
 
-    // 7) 'Ω' should be terminal (no Δ/Ξ/Π after first Ω) — char-aware scan
-    let mut seen_omega = false;
-    for (_bi, ch) in content.char_indices() {
-        if !seen_omega {
-            if ch == 'Ω' { seen_omega = true; }
-        } else {
-            if ch == 'Δ' || ch == 'Ξ' || ch == 'Π' {
-                events.push(violation("Post 'Ω' transform detected (Δ/Ξ/Π). Closure should be terminal.", Some("Ω")));
-                break;
-            }
+    // 7) 'Ω' should be terminal (no Δ/Ξ/Π after closure unless Ε re-ignites)
+    if let Some(err) = first(SemanticErrorKind::AfterClosure) {
+        let event = cfg.emit(&POST_OMEGA_TRANSFORM, "Post 'Ω' transform detected (Δ/Ξ/Π). Closure should be terminal.", Some("Ω"), at(err.span));
+        let omega = tokens.iter().rev().find(|t| t.kind == TokenKind::Symbol('Ω') && t.span.end <= err.span.start);
+        let moved = omega.map(|o| {
+            let between = &content[o.span.end..err.span.start];
+            // Only flow arrows between Ω and the transform: they are absorbed by closure anyway.
+            let safe = between.chars().all(|c| c == '→' || (c.is_whitespace() && c != '\n'));
+            let removed = if safe {
+                Span::new(o.span.end, err.span.end)
+            } else {
+                Span::new(content[..err.span.start].trim_end().len(), err.span.end)
+            };
+            let glyph = &content[err.span.start..err.span.end];
+            let insert = edit(Span::point(o.span.start), &format!("{} ", glyph));
+            fix(&format!("move {} before Ω", glyph), vec![insert, edit(removed, "")], safe)
+        });
+        events.extend(event.map(|e| match moved {
+            Some(s) => e.with_suggestion(s),
+            None => e,
+        }));
+    }
+
+    // 8) no fusion or coexistence on a field an irreversible Δ has collapsed
+    if let Some(err) = first(SemanticErrorKind::AfterCollapse) {
+        events.extend(cfg.emit(&POST_COLLAPSE_ACTION, err.message.clone(), Some(err.symbol.as_str()), at(err.span)));
+    }
+
+    // Registered rules
+    let cx = RuleContext { content, tokens: &tokens, ast: &ast, execution: &exec, gates };
+    for rule in registry.rules().filter(|r| cfg.is_enabled(r.info())) {
+        for f in rule.check(&cx) {
+            events.extend(cfg.emit(rule.info(), f.message, f.symbol.as_deref(), f.span.and_then(at)));
         }
     }
 
@@ -135,6 +184,60 @@ mod tests {
         assert!(ev.iter().any(|e| matches!(e.operator, OperatorClass::InteractionViolation) && e.symbol.as_deref()==Some("=") ));
     }
 
+    #[test]
+    fn violations_carry_byte_spans() {
+        let src = "Ψ : Φ\n[ΔΨ] → Ξ =";
+        let ev = validate_interactions(src, &ValidatorConfig::default());
+        let eq = ev.iter().find(|e| e.symbol.as_deref() == Some("=")).expect("= violation");
+        let span = eq.span.expect("span");
+        assert_eq!(&src[span.start..span.end], "=");
+        assert_eq!((span.line, span.column), (2, 10));
+
+        let src = "[Δ → [Ψ]";
+        let ev = validate_interactions(src, &ValidatorConfig::default());
+        let span = ev[0].span.expect("span");
+        assert_eq!((span.start, span.end), (0, 1));
+        assert!(ev.iter().all(|e| e.span.is_some()));
+    }
+
+    #[test]
+    fn ordering_rules_follow_execution() {
+        // Ε re-ignites after Ω, so the later Δ is not a post-closure transform.
+        let ev = validate_interactions("Ψ Ω Ε Δ", &ValidatorConfig::default());
+        assert!(ev.iter().all(|e| e.code.as_deref() != Some("PHI008")));
+        let ev = validate_interactions("Δ Ψ Δ", &ValidatorConfig::default());
+        let e = ev.iter().find(|e| e.code.as_deref() == Some("PHI009")).expect("collapse violation");
+        assert_eq!(e.span.map(|s| s.column), Some(5));
+    }
+
+    #[test]
+    fn flow_cycles_need_a_loop() {
+        let cycle = |src: &str| {
+            validate_interactions(src, &ValidatorConfig::default()).into_iter().find(|e| e.code.as_deref() == Some("PHI004"))
+        };
+        assert!(cycle("Ψ → Δ → Ξ → Ω").is_none());
+        assert!(cycle("[Ψ → Δ → Ψ] → Ω").is_none());
+        let e = cycle("Ψ → Δ → Ψ").expect("cycle notice");
+        assert!(e.message.contains("Ψ → Δ → Ψ"));
+        assert_eq!(e.span.map(|s| s.column), Some(7));
+    }
+
+    #[test]
+    fn violations_suggest_fixes() {
+        let suggestion = |src: &str, code: &str| {
+            let ev = validate_interactions(src, &ValidatorConfig::default());
+            ev.into_iter().find(|e| e.code.as_deref() == Some(code)).and_then(|e| e.suggestions.into_iter().next())
+        };
+        let s = suggestion("Δ Λ Ψ Ω =", "PHI006").expect("trailing = fix");
+        assert!(s.safe);
+        assert_eq!((s.edits[0].span.start, s.edits[0].span.end), ("Δ Λ Ψ Ω".len(), "Δ Λ Ψ Ω =".len()));
+        let s = suggestion("[Δ → [Ψ]", "PHI001").expect("missing ] fix");
+        assert_eq!(s.edits[0].replacement, "]");
+        let s = suggestion("Δ Ω → Π", "PHI008").expect("move fix");
+        assert!(s.safe && s.message == "move Π before Ω");
+        assert!(!suggestion("Δ Ω + Π", "PHI008").expect("move fix").safe);
+    }
+
     #[test]
     fn pipe_colon_same_scope_notice() {
         let ev = validate_interactions("Ψ : Φ | Γ", &ValidatorConfig::default());
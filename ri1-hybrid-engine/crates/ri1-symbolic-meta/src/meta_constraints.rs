@@ -1,8 +1,11 @@
 use ri1_core::constraints::{Consent, FieldContext, ResonanceEvent, OperatorClass};
 
+use crate::interaction::rules::{ValidatorConfig, MALFORMED_FIELD_ID, UNKNOWN_FIELD_PHASE};
+use crate::phases::FieldPhases;
+
 pub fn consent_summary(consent: &Consent) -> ResonanceEvent {
     let msg = format!(
-        "consent: {}{}{}",
+        "consent: {}{}{}{}{}",
         if consent.granted { "granted" } else { "denied" },
         consent
             .subject
@@ -13,6 +16,16 @@ pub fn consent_summary(consent: &Consent) -> ResonanceEvent {
             .section_ref
             .as_ref()
             .map(|s| format!(" ref={}", s))
+            .unwrap_or_default(),
+        consent
+            .ledger_id
+            .as_ref()
+            .map(|s| format!(" ledger={}", s))
+            .unwrap_or_default(),
+        consent
+            .reason
+            .as_ref()
+            .map(|s| format!(" reason={}", s))
             .unwrap_or_default()
     );
     ResonanceEvent {
@@ -20,29 +33,59 @@ pub fn consent_summary(consent: &Consent) -> ResonanceEvent {
         message: msg,
         section_ref: None,
         symbol: None,
+        code: None,
+        span: None,
+        suggestions: Vec::new(),
     }
 }
 
 pub fn field_protocol_notice(modality: &str, ctx: &FieldContext) -> ResonanceEvent {
     let msg = format!(
-        "field_protocol: modality={} phase={}",
+        "field_protocol: modality={} phase={}{}{}",
         modality,
-        ctx.phase.as_deref().unwrap_or("unknown")
+        ctx.phase.as_deref().unwrap_or("unknown"),
+        ctx.field_id.as_ref().map(|s| format!(" field_id={}", s)).unwrap_or_default(),
+        ctx.source.as_ref().map(|s| format!(" source={}", s)).unwrap_or_default()
     );
     ResonanceEvent {
         operator: OperatorClass::InteractionNotice,
         message: msg,
         section_ref: None,
         symbol: None,
+        code: None,
+        span: None,
+        suggestions: Vec::new(),
     }
 }
 
+/// Findings for a context naming an unknown phase or a malformed field id.
+pub fn field_context_findings(ctx: &FieldContext, phases: &FieldPhases, cfg: &ValidatorConfig) -> Vec<ResonanceEvent> {
+    let mut out = Vec::new();
+    if let Some(phase) = ctx.phase.as_deref().filter(|p| phases.phase(p).is_none()) {
+        let msg = format!("unknown field phase '{}' (expected one of {})", phase, phases.names().collect::<Vec<_>>().join(", "));
+        out.extend(cfg.emit(&UNKNOWN_FIELD_PHASE, msg, None, None));
+    }
+    if let Some(id) = ctx.field_id.as_deref().filter(|id| !well_formed_field_id(id)) {
+        out.extend(cfg.emit(&MALFORMED_FIELD_ID, format!("malformed field id '{}'", id), None, None));
+    }
+    out
+}
+
+fn well_formed_field_id(id: &str) -> bool {
+    (1..=64).contains(&id.len())
+        && id.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | ':' | '-'))
+}
+
 pub fn ethical_protocol_notice() -> ResonanceEvent {
     ResonanceEvent {
         operator: OperatorClass::InteractionNotice,
         message: "ethical_protocol: non-harm, privacy, transparency (logging-only)".into(),
         section_ref: None,
         symbol: None,
+        code: None,
+        span: None,
+        suggestions: Vec::new(),
     }
 }
 
@@ -52,6 +95,9 @@ pub fn interaction_summary(notice_count: usize, violation_count: usize) -> Reson
         message: format!("interaction_summary: notices={}, violations={}", notice_count, violation_count),
         section_ref: None,
         symbol: None,
+        code: None,
+        span: None,
+        suggestions: Vec::new(),
     }
 }
 
@@ -68,5 +114,8 @@ pub fn meta_overview(consent_count: usize, field_count: usize, ethical_count: us
         ),
         section_ref: None,
         symbol: None,
+        code: None,
+        span: None,
+        suggestions: Vec::new(),
     }
 }
@@ -0,0 +1,312 @@
+//! Declared field phases: the transitions between them and the operators each one permits or
+//! requires. `evaluate_meta` checks expressions against the phase named by the field context.
+//!
+//! The built-in declaration is the `alpha → beta → gamma → delta → omega → alpha` cycle with no
+//! operator restrictions. A TOML file declares stricter ones:
+//!
+//! ```toml
+//! [[phase]]
+//! name = "rest"
+//! deny = ["Ω"]
+//! next = [{ to = "ignition", requires = ["Ε"] }]
+//!
+//! [[phase]]
+//! name = "closing"
+//! require = ["Ω"]
+//! next = [{ to = "rest" }]
+//! ```
+//!
+//! `allow` lists the only operators a phase permits and `deny` the ones it forbids; `require`
+//! lists operators every expression in the phase must use. A transition's `requires` are the
+//! operators that advance the field: an expression using all of them implies the transition.
+//! Every listed operator must be a gated operator of the catalog, by symbol or alias.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use ri1_core::constraints::{FieldContext, ResonanceEvent, SourceSpan, Suggestion};
+use serde::Deserialize;
+
+use crate::catalog::Catalog;
+use crate::interaction::rules::{
+    ValidatorConfig, PHASE_OPERATOR, PHASE_REQUIRED_OPERATOR, PHASE_TRANSITION,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Transition {
+    pub to: String,
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phase {
+    pub name: String,
+    pub description: Option<String>,
+    /// Only these operators are permitted; all are when unset.
+    pub allow: Option<Vec<String>>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub require: Vec<String>,
+    #[serde(default)]
+    pub next: Vec<Transition>,
+}
+
+impl Phase {
+    pub fn permits(&self, glyph: &str) -> bool {
+        !self.deny.iter().any(|g| g == glyph)
+            && self.allow.as_ref().is_none_or(|a| a.iter().any(|g| g == glyph))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldPhases {
+    #[serde(rename = "phase")]
+    phases: Vec<Phase>,
+}
+
+#[derive(Debug)]
+pub enum PhaseError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+    Empty,
+    Duplicate(String),
+    UnknownTarget {
+        from: String,
+        to: String,
+    },
+    /// A glyph in `allow`, `deny`, `require` or `requires` that is not a catalog operator.
+    UnknownOperator {
+        phase: String,
+        operator: String,
+    },
+}
+
+impl fmt::Display for PhaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhaseError::Io(e) => write!(f, "cannot read field phases: {}", e),
+            PhaseError::Parse(e) => write!(f, "invalid field phases: {}", e),
+            PhaseError::Empty => write!(f, "no field phases declared"),
+            PhaseError::Duplicate(name) => write!(f, "field phase '{}' is declared twice", name),
+            PhaseError::UnknownTarget { from, to } => {
+                write!(f, "phase '{}' transitions to undeclared phase '{}'", from, to)
+            }
+            PhaseError::UnknownOperator { phase, operator } => {
+                write!(f, "phase '{}' names unknown operator '{}'", phase, operator)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PhaseError {}
+
+impl FieldPhases {
+    pub fn builtin() -> Self {
+        let cycle = ["alpha", "beta", "gamma", "delta", "omega"];
+        let phases = cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .map(|(name, next)| Phase {
+                name: name.to_string(),
+                description: None,
+                allow: None,
+                deny: Vec::new(),
+                require: Vec::new(),
+                next: vec![Transition { to: next.to_string(), requires: Vec::new() }],
+            })
+            .collect();
+        Self { phases }
+    }
+
+    /// Validates `phases` against the built-in catalog; see [`new_with`](Self::new_with).
+    pub fn new(phases: Vec<Phase>) -> Result<Self, PhaseError> {
+        Self::new_with(phases, &Catalog::builtin())
+    }
+
+    /// Checks that phase names are unique, transitions lead to declared phases and every
+    /// listed operator is a gated operator of `catalog`. Aliases are stored as the symbol.
+    pub fn new_with(mut phases: Vec<Phase>, catalog: &Catalog) -> Result<Self, PhaseError> {
+        if phases.is_empty() {
+            return Err(PhaseError::Empty);
+        }
+        let mut names = HashSet::new();
+        if let Some(p) = phases.iter().find(|p| !names.insert(p.name.as_str())) {
+            return Err(PhaseError::Duplicate(p.name.clone()));
+        }
+        for p in &phases {
+            if let Some(t) = p.next.iter().find(|t| !names.contains(t.to.as_str())) {
+                return Err(PhaseError::UnknownTarget { from: p.name.clone(), to: t.to.clone() });
+            }
+        }
+        for p in &mut phases {
+            let listed = p.allow.iter_mut().flatten().chain(&mut p.deny).chain(&mut p.require);
+            for glyph in listed.chain(p.next.iter_mut().flat_map(|t| &mut t.requires)) {
+                match catalog.operator(glyph).filter(|o| o.gated) {
+                    Some(op) => *glyph = op.symbol.clone(),
+                    None => {
+                        return Err(PhaseError::UnknownOperator {
+                            phase: p.name.clone(),
+                            operator: glyph.clone(),
+                        })
+                    }
+                }
+            }
+        }
+        Ok(Self { phases })
+    }
+
+    pub fn from_toml_str(src: &str) -> Result<Self, PhaseError> {
+        Self::from_toml_str_with(src, &Catalog::builtin())
+    }
+
+    pub fn from_toml_str_with(src: &str, catalog: &Catalog) -> Result<Self, PhaseError> {
+        let parsed: FieldPhases = toml::from_str(src).map_err(PhaseError::Parse)?;
+        Self::new_with(parsed.phases, catalog)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PhaseError> {
+        Self::load_with(path, &Catalog::builtin())
+    }
+
+    /// Reads a declaration whose operators come from `catalog`, e.g. `engine.catalog()`.
+    pub fn load_with(path: impl AsRef<Path>, catalog: &Catalog) -> Result<Self, PhaseError> {
+        Self::from_toml_str_with(&std::fs::read_to_string(path).map_err(PhaseError::Io)?, catalog)
+    }
+
+    pub fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    pub fn phase(&self, name: &str) -> Option<&Phase> {
+        self.phases.iter().find(|p| p.name == name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.phases.iter().map(|p| p.name.as_str())
+    }
+}
+
+impl Default for FieldPhases {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// Findings for the operators `present` in `content` (glyph and byte offset, in order of
+/// appearance) against the context's phase. Unknown phases are left to the field-context check.
+pub fn phase_findings(
+    phases: &FieldPhases,
+    ctx: &FieldContext,
+    content: &str,
+    present: &[(&str, usize)],
+    cfg: &ValidatorConfig,
+) -> Vec<ResonanceEvent> {
+    let Some(phase) = ctx.phase.as_deref().and_then(|p| phases.phase(p)) else { return Vec::new() };
+    let used = |glyph: &str| present.iter().any(|(g, _)| *g == glyph);
+    let span = |at: usize, glyph: &str| SourceSpan::locate(content, at, at + glyph.len());
+    let advance = |t: &Transition| {
+        let missing: Vec<_> = t.requires.iter().filter(|g| !used(g)).map(String::as_str).collect();
+        let requires = if missing.is_empty() {
+            String::new()
+        } else {
+            format!(" (requires {})", missing.join(" "))
+        };
+        Suggestion {
+            message: format!("advance the field to phase {}{}", t.to, requires),
+            edits: Vec::new(),
+            safe: false,
+        }
+    };
+    let mut out = Vec::new();
+    for &(glyph, at) in present.iter().filter(|(g, _)| !phase.permits(g)) {
+        let msg = format!("{} is not permitted in phase {}", glyph, phase.name);
+        let Some(event) = cfg.emit(&PHASE_OPERATOR, msg, Some(glyph), Some(span(at, glyph))) else {
+            continue;
+        };
+        // Point at a phase reachable from here that permits the operator.
+        let target =
+            phase.next.iter().find(|t| phases.phase(&t.to).is_some_and(|p| p.permits(glyph)));
+        out.push(match target {
+            Some(t) => event.with_suggestion(advance(t)),
+            None => event,
+        });
+    }
+    for glyph in phase.require.iter().filter(|g| !used(g)) {
+        out.extend(cfg.emit(
+            &PHASE_REQUIRED_OPERATOR,
+            format!("phase {} requires {}", phase.name, glyph),
+            Some(glyph),
+            None,
+        ));
+    }
+    for t in
+        phase.next.iter().filter(|t| !t.requires.is_empty() && t.requires.iter().all(|g| used(g)))
+    {
+        let first = present
+            .iter()
+            .find(|(g, _)| t.requires.iter().any(|r| r == g))
+            .map(|&(g, at)| span(at, g));
+        let msg = format!("expression implies advancing the field from {} to {}", phase.name, t.to);
+        out.extend(
+            cfg.emit(&PHASE_TRANSITION, msg, None, first).map(|e| e.with_suggestion(advance(t))),
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHASES: &str = r#"
+        [[phase]]
+        name = "rest"
+        deny = ["Ω"]
+        next = [{ to = "ignition", requires = ["Ε"] }]
+
+        [[phase]]
+        name = "ignition"
+        next = [{ to = "closing" }]
+
+        [[phase]]
+        name = "closing"
+        require = ["Ω"]
+        next = [{ to = "rest" }]
+    "#;
+
+    #[test]
+    fn declarations_are_validated() {
+        let p = FieldPhases::from_toml_str(PHASES).unwrap();
+        assert!(!p.phase("rest").unwrap().permits("Ω"));
+        assert_eq!(
+            FieldPhases::builtin().names().collect::<Vec<_>>(),
+            ["alpha", "beta", "gamma", "delta", "omega"]
+        );
+        let bad = "[[phase]]\nname = \"a\"\nnext = [{ to = \"b\" }]\n";
+        assert!(matches!(FieldPhases::from_toml_str(bad), Err(PhaseError::UnknownTarget { .. })));
+        let dup = "[[phase]]\nname = \"a\"\n[[phase]]\nname = \"a\"\n";
+        assert!(matches!(FieldPhases::from_toml_str(dup), Err(PhaseError::Duplicate(_))));
+    }
+
+    #[test]
+    fn operators_must_be_in_the_catalog() {
+        let ascii = "[[phase]]\nname = \"rest\"\ndeny = [\"Omega\"]\n";
+        let err = FieldPhases::from_toml_str(ascii).unwrap_err();
+        assert_eq!(err.to_string(), "phase 'rest' names unknown operator 'Omega'");
+        let modifier = "[[phase]]\nname = \"a\"\nnext = [{ to = \"a\", requires = [\"ⁿ\"] }]\n";
+        assert!(matches!(
+            FieldPhases::from_toml_str(modifier),
+            Err(PhaseError::UnknownOperator { .. })
+        ));
+        // Aliases are read as the operator's symbol.
+        let alias =
+            FieldPhases::from_toml_str("[[phase]]\nname = \"a\"\nallow = [\"ρ\"]\n").unwrap();
+        assert!(alias.phase("a").unwrap().permits("Ρ"));
+    }
+}
@@ -0,0 +1,70 @@
+use ri1_core::constraints::{FieldContext, MetaEngine, OperatorClass};
+use ri1_symbolic_meta::{Catalog, CatalogError, MetaEngineImpl};
+
+const SPEC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../docs/spec/phipe-catalog.toml");
+
+#[test]
+fn shipped_spec_matches_builtin() {
+    let mut file = Catalog::load(SPEC).unwrap();
+    for o in &mut file.operators {
+        o.source = None;
+    }
+    assert_eq!(file, Catalog::builtin());
+}
+
+#[test]
+fn revisions_load_without_recompiling() {
+    let src = r#"
+        [[operators]]
+        symbol = "Φ"
+        name = "HarmonicEquilibrium"
+        class = "HarmonicStabilization"
+        section = "101"
+
+        [[operators]]
+        symbol = "κ"
+        name = "Kernel"
+        class = "Coexistence"
+        gate = false
+
+        [[conditionals]]
+        symbol = "→"
+        name = "FlowVector"
+        class = "FlowVector"
+    "#;
+    let path = std::env::temp_dir().join(format!("ri1-catalog-{}.toml", std::process::id()));
+    std::fs::write(&path, src).unwrap();
+    let eng = MetaEngineImpl::from_catalog(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    let keys: Vec<_> = eng.operators().iter().map(|o| o.key.as_str()).collect();
+    assert_eq!(keys, ["HarmonicEquilibrium", "Kernel"]);
+    assert_eq!(eng.gates().symbols(), ["Φ"]);
+    let (_c, events) = eng.evaluate_meta("text", "Φ → Ψ + Ω", &FieldContext::default());
+    let phi = events.iter().find(|e| e.operator == OperatorClass::HarmonicStabilization).unwrap();
+    assert_eq!(phi.section_ref.as_deref(), Some("101"));
+    assert!(events.iter().any(|e| e.message == "→: FlowVector"));
+    assert!(events.iter().all(|e| e.operator != OperatorClass::Simultaneity
+        && e.operator != OperatorClass::ClosureIntegration));
+}
+
+#[test]
+fn json_catalogs_load() {
+    let src = r#"{"operators": [{"symbol": "Ρ", "name": "PerceptionModulation", "class": "PerceptionModulation", "aliases": ["ρ"]}]}"#;
+    let c = Catalog::from_json_str(src).unwrap();
+    assert_eq!(c.operator("ρ").map(|o| o.class), Some(OperatorClass::PerceptionModulation));
+}
+
+#[test]
+fn rejects_inconsistent_catalogs() {
+    let dup =
+        "[[operators]]\nsymbol = \"Ρ\"\nname = \"A\"\nclass = \"Oscillation\"\naliases = [\"ρ\"]\n\
+               [[operators]]\nsymbol = \"ρ\"\nname = \"B\"\nclass = \"Oscillation\"\n";
+    assert!(matches!(Catalog::from_toml_str(dup), Err(CatalogError::Duplicate(g)) if g == "ρ"));
+    let unknown = "[[operators]]\nsymbol = \"Φ\"\nname = \"A\"\nclass = \"Harmony\"\n";
+    assert!(
+        matches!(Catalog::from_toml_str(unknown), Err(CatalogError::UnknownClass { class, .. }) if class == "Harmony")
+    );
+    let ungated = "[[operators]]\nsymbol = \"κ\"\nname = \"Kernel\"\nclass = \"Coexistence\"\n";
+    let err = MetaEngineImpl::from_catalog_spec(Catalog::from_toml_str(ungated).unwrap()).err();
+    assert!(matches!(err, Some(CatalogError::NoGate(s)) if s == "κ"));
+}
@@ -0,0 +1,24 @@
+use ri1_core::modality::{GenerationRequest, GenerationResponse, Modality};
+use ri1_core::Orchestrator;
+use ri1_symbolic_meta::MetaEngineImpl;
+
+/// Generates its prompt unchanged, so tests control exactly what is evaluated.
+pub struct Echo;
+
+impl Modality for Echo {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn generate(&self, req: GenerationRequest) -> GenerationResponse {
+        GenerationResponse { content: req.prompt }
+    }
+}
+
+/// An orchestrator with [`Echo`] registered and `engine` as its meta engine.
+pub fn orchestrator(engine: MetaEngineImpl) -> Orchestrator {
+    let mut orch = Orchestrator::new();
+    orch.register_modality(Echo);
+    orch.set_meta_engine(engine);
+    orch
+}
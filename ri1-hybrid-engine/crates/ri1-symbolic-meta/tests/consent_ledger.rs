@@ -0,0 +1,34 @@
+use ri1_core::constraints::{FieldContext, MetaEngine};
+use ri1_symbolic_meta::{ConsentLedger, MetaEngineImpl};
+
+fn consent_message(ledger: ConsentLedger, subject: &str) -> String {
+    let eng = MetaEngineImpl::new_default().with_consent_ledger(ledger);
+    let ctx = FieldContext {
+        subject: Some(subject.into()),
+        modality: Some("text".into()),
+        ..FieldContext::default()
+    };
+    let (_, events) = eng.evaluate_meta("text", "Ψ", &ctx);
+    events[0].message.clone()
+}
+
+#[test]
+fn engine_decides_through_the_ledger() {
+    let path = std::env::temp_dir().join(format!("ri1-engine-ledger-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut ledger = ConsentLedger::open(&path).unwrap();
+    ledger.grant("alice", vec!["text".into()], None, None).unwrap();
+    assert_eq!(
+        consent_message(ledger.clone(), "alice"),
+        "consent: granted subject=alice ref=§Π ledger=C0001 reason=ledger grant C0001"
+    );
+    assert_eq!(
+        consent_message(ledger.clone(), "bob"),
+        "consent: denied subject=bob ref=§Π reason=no ledger grant for subject bob"
+    );
+
+    ledger.revoke("C0001", None).unwrap();
+    let msg = consent_message(ConsentLedger::open(&path).unwrap(), "alice");
+    assert_eq!(msg, "consent: denied subject=alice ref=§Π ledger=C0001 reason=ledger grant C0001 revoked by C0002");
+    std::fs::remove_file(&path).unwrap();
+}
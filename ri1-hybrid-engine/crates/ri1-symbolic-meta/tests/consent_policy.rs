@@ -0,0 +1,64 @@
+mod common;
+
+use ri1_core::constraints::{FieldContext, MetaEngine};
+use ri1_core::modality::GenerationRequest;
+use ri1_core::{
+    InoculationBlock, InoculationGuard, InoculationLimits, Orchestrator, OrchestratorError,
+};
+use ri1_symbolic_meta::{DeclarativePolicy, MetaEngineImpl};
+
+fn orchestrator(policy: &str) -> Orchestrator {
+    common::orchestrator(
+        MetaEngineImpl::new_default()
+            .with_consent_policy(DeclarativePolicy::from_toml_str(policy).unwrap()),
+    )
+}
+
+#[test]
+fn denied_consent_blocks_generation() {
+    let req = || GenerationRequest::new("Ψ → Ω");
+    match orchestrator("default = \"deny\"").generate("echo", req()) {
+        Err(OrchestratorError::ConsentDenied(c)) => {
+            assert_eq!(c.reason.as_deref(), Some("no policy rule matched"))
+        }
+        other => panic!("expected a consent denial, got {:?}", other.map(|r| r.content)),
+    }
+    assert!(orchestrator("[[rule]]\neffect = \"grant\"\nmodality = \"echo\"")
+        .generate("echo", req())
+        .is_ok());
+}
+
+#[test]
+fn consent_summary_carries_the_decision() {
+    let policy = "[[rule]]\neffect = \"deny\"\nphase = \"omega\"\nreason = \"field closed\"\n";
+    let eng = MetaEngineImpl::new_default()
+        .with_consent_policy(DeclarativePolicy::from_toml_str(policy).unwrap());
+    let ctx = FieldContext { phase: Some("omega".into()), ..FieldContext::default() };
+    let (_, events) = eng.evaluate_meta("text", "Ψ", &ctx);
+    assert_eq!(events[0].message, "consent: denied ref=§Π reason=field closed");
+    let (_, events) =
+        MetaEngineImpl::new_default().evaluate_meta("text", "Ψ", &FieldContext::default());
+    assert_eq!(events[0].message, "consent: granted subject=default ref=§Π");
+}
+
+#[test]
+fn denied_requests_do_not_count_as_repetitions() {
+    let policy = "default = \"grant\"\n[[rule]]\neffect = \"deny\"\nphase = \"omega\"\n";
+    let mut orch = orchestrator(policy);
+    orch.set_inoculation_guard(InoculationGuard::new(InoculationLimits {
+        max_repetitions: 1,
+        ..InoculationLimits::default()
+    }));
+    let omega = FieldContext { phase: Some("omega".into()), ..FieldContext::default() };
+    for _ in 0..3 {
+        let denied = orch.generate_in_context("echo", GenerationRequest::new("Ψ → Ω"), &omega);
+        assert!(matches!(denied, Err(OrchestratorError::ConsentDenied(_))));
+    }
+    assert!(orch.generate("echo", GenerationRequest::new("Ψ → Ω")).is_ok());
+    match orch.generate("echo", GenerationRequest::new("Ψ → Ω")) {
+        Err(OrchestratorError::Inoculation(block)) => {
+            assert_eq!(block, InoculationBlock::Repetition { count: 2, limit: 1 })
+        }
+        other => panic!("expected a repetition block, got {:?}", other.map(|r| r.content)),
+    }
+}
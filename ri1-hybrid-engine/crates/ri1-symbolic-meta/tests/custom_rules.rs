@@ -0,0 +1,88 @@
+use ri1_core::constraints::{FieldContext, MetaEngine, OperatorClass};
+use ri1_symbolic_meta::{
+    ExprKind, Finding, GateRegistry, InteractionRule, MetaEngineImpl, RuleContext, RuleError,
+    RuleInfo, RuleRegistry, RuleSeverity, ValidatorConfig,
+};
+
+/// Project rule: Π opens recursion across scales and must never sit inside a `[]` loop.
+struct NoPiInLoop;
+
+const NO_PI_IN_LOOP: RuleInfo = RuleInfo {
+    code: "ACME001",
+    name: "no-pi-in-loop",
+    severity: RuleSeverity::Violation,
+    help: "lift Π out of the loop container",
+};
+
+impl InteractionRule for NoPiInLoop {
+    fn info(&self) -> &RuleInfo {
+        &NO_PI_IN_LOOP
+    }
+
+    fn check(&self, cx: &RuleContext<'_>) -> Vec<Finding> {
+        let mut out = Vec::new();
+        if let Some(root) = &cx.ast.root {
+            root.walk(&mut |e, depth| match &e.kind {
+                ExprKind::Symbol(s) if depth > 0 && s.is("Π") => {
+                    out.push(Finding::new(
+                        "Π inside a '[]' loop container",
+                        Some("Π"),
+                        Some(e.span),
+                    ));
+                }
+                _ => {}
+            });
+        }
+        out
+    }
+}
+
+fn engine() -> MetaEngineImpl {
+    let mut eng = MetaEngineImpl::new_default();
+    eng.register_rule(NoPiInLoop).unwrap();
+    eng
+}
+
+#[test]
+fn registered_rules_emit_interaction_events() {
+    let src = "Ψ → [Δ Π] → Ω";
+    let (_c, events) = engine().evaluate_meta("text", src, &FieldContext::default());
+    let e =
+        events.iter().find(|e| e.code.as_deref() == Some("ACME001")).expect("custom rule event");
+    assert_eq!(e.operator, OperatorClass::InteractionViolation);
+    let span = e.span.expect("span");
+    assert_eq!(&src[span.start..span.end], "Π");
+    let summary =
+        events.iter().find(|e| e.message.starts_with("interaction_summary")).expect("summary");
+    assert_eq!(summary.message, "interaction_summary: notices=0, violations=1");
+
+    let (_c, events) = engine().evaluate_meta("text", "Π → [Δ Ψ]", &FieldContext::default());
+    assert!(events.iter().all(|e| e.code.as_deref() != Some("ACME001")));
+}
+
+#[test]
+fn registered_rules_are_configurable() {
+    let mut registry = RuleRegistry::default();
+    registry.register(NoPiInLoop).unwrap();
+    let cfg = ValidatorConfig::from_toml_str_with(
+        "[rules.no-pi-in-loop]\nseverity = \"hard\"\n",
+        &registry,
+        &GateRegistry::builtin(),
+    )
+    .unwrap();
+    assert!(ValidatorConfig::from_toml_str("[rules.ACME001]\n").is_err());
+
+    let eng = engine().with_validator_config(cfg);
+    let (results, _e) = eng.evaluate_meta("text", "[Π]", &FieldContext::default());
+    assert!(results.iter().any(|r| r.name == "ACME001" && !r.passed));
+}
+
+#[test]
+fn duplicate_codes_are_rejected() {
+    let mut eng = engine();
+    assert_eq!(
+        eng.register_rule(NoPiInLoop),
+        Err(RuleError::Duplicate { code: "ACME001", name: "no-pi-in-loop" })
+    );
+    assert_eq!(eng.rules().rules().count(), 1);
+}
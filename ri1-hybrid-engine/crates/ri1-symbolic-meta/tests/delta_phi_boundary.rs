@@ -0,0 +1,20 @@
+use ri1_core::constraints::{FieldContext, MetaEngine, OperatorClass};
+use ri1_symbolic_meta::MetaEngineImpl;
+
+fn fusion_message(content: &str) -> String {
+    let eng = MetaEngineImpl::new_default();
+    let (_c, events) = eng.evaluate_meta("text", content, &FieldContext::default());
+    events.into_iter().find(|e| e.operator == OperatorClass::Fusion).expect("Δ event").message
+}
+
+#[test]
+fn phi_adjacent_to_delta_prevents_fusion() {
+    assert_eq!(fusion_message("ΕΔΦ → Ω"), "Δ fusion prevented across Φ boundary");
+    assert_eq!(fusion_message("Φ Δ"), "Δ fusion prevented across Φ boundary");
+}
+
+#[test]
+fn phi_elsewhere_does_not_prevent_fusion() {
+    assert_eq!(fusion_message("Φ + ΔΨ"), "Δ fusion collapse enacted");
+    assert_eq!(fusion_message("[ΔΨ] → Φ"), "Δ fusion collapse enacted");
+}
@@ -0,0 +1,23 @@
+use ri1_core::constraints::{FieldContext, MetaEngine, OperatorClass};
+use ri1_symbolic_meta::MetaEngineImpl;
+
+#[test]
+fn gate_and_interaction_events_point_at_their_symbol() {
+    let eng = MetaEngineImpl::new_default();
+    let content = "TEXT: Ψ : Φ / Ψ | Γ\n[ΔΛ] → Ω = Π";
+    let (_c, events) = eng.evaluate_meta("text", content, &FieldContext::default());
+    for e in events.iter().filter(|e| e.symbol.is_some()) {
+        let span = e.span.unwrap_or_else(|| panic!("missing span on {:?}", e));
+        assert!(content.is_char_boundary(span.start) && content.is_char_boundary(span.end));
+        if e.operator != OperatorClass::InteractionViolation
+            && e.operator != OperatorClass::InteractionNotice
+        {
+            let sym = e.symbol.as_deref().unwrap();
+            let expected = if sym == "[]" { "[" } else { sym };
+            assert_eq!(&content[span.start..span.end], expected, "{:?}", e);
+        }
+    }
+    let omega = events.iter().find(|e| e.operator == OperatorClass::ClosureIntegration).unwrap();
+    let span = omega.span.unwrap();
+    assert_eq!((span.line, span.column), (2, 8));
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use ri1_core::constraints::FieldContext;
+use ri1_core::modality::GenerationRequest;
+use ri1_core::{Orchestrator, OrchestratorError};
+use ri1_symbolic_meta::{MetaEngineImpl, ValidatorConfig};
+
+fn orchestrator(cfg: &str) -> Orchestrator {
+    common::orchestrator(
+        MetaEngineImpl::new_default()
+            .with_validator_config(ValidatorConfig::from_toml_str(cfg).unwrap()),
+    )
+}
+
+fn ctx(phase: &str, field_id: &str) -> FieldContext {
+    FieldContext {
+        phase: Some(phase.into()),
+        field_id: Some(field_id.into()),
+        source: Some("test".into()),
+        ..FieldContext::default()
+    }
+}
+
+#[test]
+fn context_reaches_field_protocol_events() {
+    let (_, events) =
+        orchestrator("").evaluate_with_events_in_context("echo", "Ψ", &ctx("beta", "field-7"));
+    assert!(events
+        .iter()
+        .any(|e| e.message
+            == "field_protocol: modality=echo phase=beta field_id=field-7 source=test"));
+    assert!(!events.iter().any(|e| e.code.is_some()));
+}
+
+#[test]
+fn invalid_context_is_reported_and_can_block() {
+    let (_, events) =
+        orchestrator("").evaluate_with_events_in_context("echo", "Ψ", &ctx("zeta", "-x"));
+    let codes: Vec<_> = events.iter().filter_map(|e| e.code.as_deref()).collect();
+    assert_eq!(codes, ["PHI011", "PHI012"]);
+
+    let req = || GenerationRequest::new("Ψ");
+    assert!(orchestrator("").generate_in_context("echo", req(), &ctx("zeta", "f")).is_ok());
+    let strict = orchestrator("[rules.unknown-field-phase]\nseverity = \"hard\"\n");
+    match strict.generate_in_context("echo", req(), &ctx("zeta", "f")) {
+        Err(OrchestratorError::HardConstraints { modality, failures }) => {
+            assert_eq!(modality, "echo");
+            assert_eq!(failures.iter().map(|r| r.name).collect::<Vec<_>>(), ["PHI011"]);
+        }
+        other => panic!("expected a hard-constraint failure, got {:?}", other.map(|r| r.content)),
+    }
+    assert!(strict.generate_in_context("echo", req(), &ctx("omega", "f")).is_ok());
+}
@@ -0,0 +1,37 @@
+use ri1_core::constraints::{FieldContext, MetaEngine, ResonanceEvent};
+use ri1_symbolic_meta::{FieldPhases, MetaEngineImpl};
+
+const SPEC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../docs/spec/field-phases.toml");
+
+fn findings(phase: &str, content: &str) -> Vec<ResonanceEvent> {
+    let eng = MetaEngineImpl::new_default().with_field_phases(FieldPhases::load(SPEC).unwrap());
+    let ctx = FieldContext { phase: Some(phase.into()), ..FieldContext::default() };
+    let (_, events) = eng.evaluate_meta("text", content, &ctx);
+    events.into_iter().filter(|e| e.code.is_some()).collect()
+}
+
+#[test]
+fn forbidden_operator_suggests_advancing() {
+    let ev = findings("growth", "Ψ → Ω");
+    let omega = ev.iter().find(|e| e.code.as_deref() == Some("PHI013")).expect("Ω flagged");
+    assert_eq!(omega.message, "Ω is not permitted in phase growth");
+    assert_eq!(omega.span.map(|s| s.column), Some(5));
+    assert_eq!(omega.suggestions[0].message, "advance the field to phase closing");
+    // Δ is permitted one step on, in ignition, which only Ε leads to.
+    let ev = findings("rest", "Δ Ψ");
+    assert_eq!(ev[0].suggestions[0].message, "advance the field to phase ignition (requires Ε)");
+    assert!(findings("closing", "Ψ → Ω").is_empty());
+}
+
+#[test]
+fn required_operators_and_implied_transitions() {
+    let ev = findings("closing", "Ψ");
+    assert_eq!(ev.iter().filter_map(|e| e.code.as_deref()).collect::<Vec<_>>(), ["PHI014"]);
+    let ev = findings("rest", "Ε Ψ");
+    let t = ev.iter().find(|e| e.code.as_deref() == Some("PHI015")).expect("transition implied");
+    assert_eq!(t.message, "expression implies advancing the field from rest to ignition");
+    assert_eq!(t.suggestions[0].message, "advance the field to phase ignition");
+    // Unknown phases are reported once, by the field-context check.
+    let ev = findings("alpha", "Ω");
+    assert_eq!(ev.iter().filter_map(|e| e.code.as_deref()).collect::<Vec<_>>(), ["PHI011"]);
+}
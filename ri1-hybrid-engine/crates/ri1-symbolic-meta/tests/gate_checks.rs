@@ -0,0 +1,117 @@
+use ri1_core::constraints::{
+    ConstraintResult, FieldContext, GateContext, GateOutcome, MetaEngine, OperatorClass,
+    OperatorGate, Severity,
+};
+use ri1_symbolic_meta::{
+    ConfigError, GateBinding, GateCheck, MetaEngineImpl, OutcomeFlag, ValidatorConfig,
+};
+
+fn results(cfg: &str, content: &str) -> Vec<ConstraintResult> {
+    let eng = MetaEngineImpl::new_default()
+        .with_validator_config(ValidatorConfig::from_toml_str(cfg).unwrap());
+    eng.evaluate_meta("text", content, &FieldContext::default()).0
+}
+
+fn check<'r>(results: &'r [ConstraintResult], name: &str) -> Option<&'r ConstraintResult> {
+    results.iter().find(|r| r.name == name)
+}
+
+#[test]
+fn delta_across_phi_fails_hard() {
+    let r = results("", "Ψ Δ Φ");
+    let c = check(&r, "delta_phi_boundary").expect("Δ check");
+    assert!(!c.passed && c.severity == Severity::Hard);
+    assert_eq!(c.span.map(|s| s.column), Some(3));
+
+    let r = results("", "Ψ Δ → Φ");
+    assert!(check(&r, "delta_phi_boundary").is_some_and(|c| c.passed));
+    // Gates that do not occur contribute no results.
+    assert!(check(&r, "pi_support").is_none());
+}
+
+fn all<'r>(results: &'r [ConstraintResult], name: &str) -> Vec<&'r ConstraintResult> {
+    results.iter().filter(|r| r.name == name).collect()
+}
+
+#[test]
+fn every_occurrence_is_checked() {
+    // The verdict does not depend on which operand comes first.
+    for src in ["Ψ Δ | Φ Δ", "Φ Δ | Ψ Δ"] {
+        let r = results("", src);
+        let c = all(&r, "delta_phi_boundary");
+        assert_eq!(c.iter().map(|c| c.passed).filter(|p| !p).count(), 1, "{}", src);
+        assert_eq!(c.len(), 2);
+    }
+    // Each Λ is judged by its own thread.
+    let r = results("", "Λ | Δ Λ");
+    let c = all(&r, "lambda_precursor");
+    assert_eq!(
+        c.iter().map(|c| (c.passed, c.span.map(|s| s.column))).collect::<Vec<_>>(),
+        [(false, Some(1)), (true, Some(7))]
+    );
+}
+
+#[test]
+fn soft_checks_follow_the_neighbourhood() {
+    let r = results("", "Π Ψ");
+    let c = check(&r, "pi_support").expect("Π check");
+    assert!(!c.passed && c.severity == Severity::Soft);
+    assert!(check(&results("", "Φ Π"), "pi_support").is_some_and(|c| c.passed));
+    assert!(check(&results("", "Λ Ψ"), "lambda_precursor").is_some_and(|c| !c.passed));
+    assert!(check(&results("", "ω Ω"), "will_force_closure").is_some_and(|c| !c.passed));
+}
+
+#[test]
+fn config_overrides_gate_checks() {
+    let r = results("[gates.delta_phi_boundary]\nseverity = \"soft\"\n", "Ψ Δ Φ");
+    assert_eq!(check(&r, "delta_phi_boundary").map(|c| c.severity), Some(Severity::Soft));
+    let cfg =
+        "[gates.delta_phi_boundary]\nenabled = false\n[gates.pi_support]\nseverity = \"hard\"\n";
+    assert!(check(&results(cfg, "Ψ Δ Φ"), "delta_phi_boundary").is_none());
+    let r = results(cfg, "Π Ψ");
+    assert!(check(&r, "pi_support").is_some_and(|c| !c.passed && c.severity == Severity::Hard));
+    assert!(matches!(
+        ValidatorConfig::from_toml_str("[gates.nope]\n"),
+        Err(ConfigError::UnknownGateCheck(_))
+    ));
+}
+
+/// Downstream gate with a check of its own.
+struct Kappa;
+
+impl OperatorGate for Kappa {
+    fn symbol(&self) -> &'static str {
+        "κ"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        GateOutcome {
+            stabilized: !cx.preceding.is_empty(),
+            prevented_fusion: true,
+            prevented_disruption: true,
+            note: None,
+        }
+    }
+}
+
+const KAPPA_OPERAND: GateCheck = GateCheck {
+    name: "kappa_operand",
+    flag: OutcomeFlag::Stabilized,
+    expected: true,
+    severity: Severity::Soft,
+    message: "κ needs an operand before it",
+};
+
+#[test]
+fn custom_gate_checks_are_configurable() {
+    let mut eng = MetaEngineImpl::new_default();
+    eng.register_gate(
+        GateBinding::new(Kappa, OperatorClass::Oscillation, None).check(KAPPA_OPERAND),
+    );
+    let src = "[gates.kappa_operand]\nseverity = \"hard\"\n";
+    assert!(matches!(ValidatorConfig::from_toml_str(src), Err(ConfigError::UnknownGateCheck(_))));
+    let cfg = ValidatorConfig::from_toml_str_with(src, eng.rules(), eng.gates()).unwrap();
+    let (r, _e) =
+        eng.with_validator_config(cfg).evaluate_meta("text", "κ Ψ", &FieldContext::default());
+    assert!(check(&r, "kappa_operand").is_some_and(|c| !c.passed && c.severity == Severity::Hard));
+}
@@ -0,0 +1,30 @@
+use ri1_core::constraints::{FieldContext, MetaEngine, OperatorClass};
+use ri1_symbolic_meta::MetaEngineImpl;
+
+fn message(content: &str, class: OperatorClass) -> String {
+    let eng = MetaEngineImpl::new_default();
+    let (_c, events) = eng.evaluate_meta("text", content, &FieldContext::default());
+    events.into_iter().find(|e| e.operator == class).expect("gate event").message
+}
+
+#[test]
+fn illumination_needs_a_preceding_thread() {
+    let lit = "Λ renders structural clarity from recursive fields";
+    let attempt = "Λ encountered without precursor Δ/Ψ; marking as illumination attempt";
+    assert_eq!(message("Δ Ψ → Λ", OperatorClass::StructuralIllumination), lit);
+    // Δ after Λ, or in an orthogonal field, does not feed it.
+    assert_eq!(message("Λ → Δ", OperatorClass::StructuralIllumination), attempt);
+    assert_eq!(message("Δ | Λ", OperatorClass::StructuralIllumination), attempt);
+}
+
+#[test]
+fn closure_pairing_follows_the_thread() {
+    assert_eq!(
+        message("ω → Ω", OperatorClass::WillForce),
+        "ω present with Ω; will-force cannot persist into closure"
+    );
+    assert_eq!(
+        message("Ω Ε ω", OperatorClass::WillForce),
+        "ω encodes autogenic intention vector within recursion"
+    );
+}
@@ -0,0 +1,109 @@
+use ri1_core::constraints::{
+    FieldContext, GateContext, GateOutcome, MetaEngine, OperatorClass, OperatorGate, ResonanceEvent,
+};
+use ri1_symbolic_meta::{execute, GateBinding, MetaEngineImpl};
+
+/// Team override: Ω only stabilizes when nothing follows it in its thread.
+struct StrictOmega;
+
+impl OperatorGate for StrictOmega {
+    fn symbol(&self) -> &'static str {
+        "Ω"
+    }
+
+    fn apply(&self, cx: &GateContext<'_>) -> GateOutcome {
+        let terminal = cx.following.is_empty();
+        GateOutcome {
+            stabilized: terminal,
+            prevented_fusion: true,
+            prevented_disruption: terminal,
+            note: Some(if terminal {
+                "Ω strict: terminal closure".into()
+            } else {
+                "Ω strict: closure is not terminal".into()
+            }),
+        }
+    }
+}
+
+/// Team override: Δ never fuses, so nothing collapses.
+struct SoftDelta;
+
+impl OperatorGate for SoftDelta {
+    fn symbol(&self) -> &'static str {
+        "Δ"
+    }
+
+    fn apply(&self, _cx: &GateContext<'_>) -> GateOutcome {
+        GateOutcome {
+            stabilized: true,
+            prevented_fusion: true,
+            prevented_disruption: true,
+            note: Some("Δ soft: no fusion".into()),
+        }
+    }
+}
+
+struct Kappa;
+
+impl OperatorGate for Kappa {
+    fn symbol(&self) -> &'static str {
+        "κ"
+    }
+
+    fn apply(&self, _cx: &GateContext<'_>) -> GateOutcome {
+        GateOutcome {
+            stabilized: true,
+            prevented_fusion: true,
+            prevented_disruption: true,
+            note: None,
+        }
+    }
+}
+
+fn events(eng: &MetaEngineImpl, content: &str) -> Vec<ResonanceEvent> {
+    eng.evaluate_meta("text", content, &FieldContext::default()).1
+}
+
+#[test]
+fn registered_gate_overrides_builtin() {
+    let mut eng = MetaEngineImpl::new_default();
+    eng.register_gate(GateBinding::new(
+        StrictOmega,
+        OperatorClass::ClosureIntegration,
+        Some("004"),
+    ));
+    let ev = events(&eng, "Ψ Ω → Ψ");
+    let omega: Vec<_> =
+        ev.iter().filter(|e| e.operator == OperatorClass::ClosureIntegration).collect();
+    assert_eq!(omega.len(), 1);
+    assert_eq!(omega[0].message, "Ω strict: closure is not terminal");
+    assert_eq!(omega[0].section_ref.as_deref(), Some("004"));
+}
+
+#[test]
+fn new_gates_run_in_registry_order() {
+    let mut eng = MetaEngineImpl::new_default();
+    eng.gates_mut()
+        .register_before("Ψ", GateBinding::new(Kappa, OperatorClass::Oscillation, Some("900")));
+    let ev = events(&eng, "Ψ κ");
+    let order: Vec<_> = ev
+        .iter()
+        .filter(|e| e.operator == OperatorClass::Oscillation)
+        .map(|e| e.symbol.clone().unwrap())
+        .collect();
+    assert_eq!(order, ["κ", "Ψ"]);
+    let kappa = ev.iter().find(|e| e.symbol.as_deref() == Some("κ")).unwrap();
+    assert_eq!(kappa.message, "κ: Oscillation");
+}
+
+#[test]
+fn execution_runs_through_registered_gates() {
+    let mut eng = MetaEngineImpl::new_default();
+    assert!(events(&eng, "Δ Ψ Δ").iter().any(|e| e.code.as_deref() == Some("PHI009")));
+    eng.register_gate(GateBinding::new(SoftDelta, OperatorClass::Fusion, Some("002")));
+    let x = execute("Δ Ψ Δ", eng.gates());
+    assert_eq!(x.trace[0].note.as_deref(), Some("Δ soft: no fusion"));
+    assert!(!x.state.collapsed && x.errors.is_empty());
+    assert!(events(&eng, "Δ Ψ Δ").iter().all(|e| e.code.as_deref() != Some("PHI009")));
+}
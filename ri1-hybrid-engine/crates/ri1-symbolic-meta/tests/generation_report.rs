@@ -0,0 +1,34 @@
+mod common;
+
+use ri1_core::modality::GenerationRequest;
+use ri1_core::Orchestrator;
+use ri1_symbolic_meta::MetaEngineImpl;
+
+fn orchestrator() -> Orchestrator {
+    common::orchestrator(MetaEngineImpl::new_default())
+}
+
+#[test]
+fn report_carries_the_evaluation() {
+    let req =
+        GenerationRequest { prompt: "Ψ → Ω".into(), correlation_id: Some("run-1".into()) };
+    let report = orchestrator().generate("echo", req).unwrap();
+    assert_eq!(
+        (report.correlation_id.as_str(), report.modality.as_str(), report.content.as_str()),
+        ("run-1", "echo", "Ψ → Ω")
+    );
+    assert!(report.results.iter().any(|r| r.name == "non_empty" && r.passed));
+    assert!(report.events[0].message.starts_with("consent: granted"));
+    // The correlation id is a field of the report, not an event.
+    assert!(!report.events.iter().any(|e| e.message.contains("run-1")));
+    assert!(report.influence.is_some_and(|i| !i.operator_influence.is_empty()));
+    assert!(report.timings.total_us >= report.timings.generation_us + report.timings.evaluation_us);
+}
+
+#[test]
+fn correlation_ids_are_generated_when_absent() {
+    let orch = orchestrator();
+    let a = orch.generate("echo", GenerationRequest::new("Ψ")).unwrap();
+    let b = orch.generate("echo", GenerationRequest::new("Φ")).unwrap();
+    assert!(!a.correlation_id.is_empty() && a.correlation_id != b.correlation_id);
+}
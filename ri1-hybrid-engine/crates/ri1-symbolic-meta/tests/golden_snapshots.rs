@@ -29,7 +29,7 @@ fn golden_cooperative_minimal() {
     assert_eq!(got.get("conflict_count").unwrap().as_u64().unwrap(), 0);
     // resonance bound tight for cooperative case
     let r = got.get("resonance_index").unwrap().as_f64().unwrap();
-    assert!(r >= 0.95 && r <= 1.0, "expected high resonance_index, got {}", r);
+    assert!((0.95..=1.0).contains(&r), "expected high resonance_index, got {}", r);
 }
 
 #[test]
@@ -0,0 +1,72 @@
+use ri1_core::constraints::{FieldContext, MetaEngine, OperatorClass, ResonanceEvent};
+use ri1_symbolic_meta::interaction::tokenize::{tokenize, TokenKind};
+use ri1_symbolic_meta::MetaEngineImpl;
+
+fn events(content: &str) -> Vec<ResonanceEvent> {
+    MetaEngineImpl::new_default().evaluate_meta("text", content, &FieldContext::default()).1
+}
+
+#[test]
+fn latin_lookalikes_reach_their_gates() {
+    let eng = MetaEngineImpl::new_default().with_alias_input(true);
+    let ev = eng.evaluate_meta("text", "E Δ → Ω", &FieldContext::default()).1;
+    let ignition = ev.iter().find(|e| e.operator == OperatorClass::Ignition).expect("Ε gate");
+    // Spans refer to the input as typed.
+    assert_eq!(ignition.span.map(|s| (s.start, s.end)), Some((0, 1)));
+    let notice =
+        ev.iter().find(|e| e.code.as_deref() == Some("PHI010")).expect("confusable notice");
+    assert_eq!(notice.operator, OperatorClass::InteractionNotice);
+    assert!(notice.message.contains("U+0045 → U+0395"), "{}", notice.message);
+    let omega = ev.iter().find(|e| e.operator == OperatorClass::ClosureIntegration).unwrap();
+    assert_eq!(omega.span.map(|s| (s.start, s.column)), Some(("E Δ → ".len(), 7)));
+}
+
+#[test]
+fn lookalikes_in_prose_are_only_flagged() {
+    let ev = events("Take vitamin E daily; a P value below 0.05");
+    assert!(ev.iter().all(|e| e.operator != OperatorClass::Ignition
+        && e.operator != OperatorClass::PerceptionModulation));
+    let notices: Vec<_> = ev.iter().filter(|e| e.code.as_deref() == Some("PHI010")).collect();
+    assert_eq!(notices.len(), 2);
+    assert_eq!(notices[0].suggestions[0].edits[0].replacement, "Ε");
+}
+
+#[test]
+fn non_operator_lookalikes_are_only_flagged() {
+    let ev = events("Ψ X");
+    let notice =
+        ev.iter().find(|e| e.code.as_deref() == Some("PHI010")).expect("confusable notice");
+    assert!(notice.message.contains("U+0058"), "{}", notice.message);
+    assert_eq!(notice.suggestions[0].edits[0].replacement, "Χ");
+    assert!(events("TEXT: Ψ").iter().all(|e| e.code.as_deref() != Some("PHI010")));
+}
+
+#[test]
+fn combining_marks_join_their_symbol() {
+    let tokens = tokenize("Γ\u{0307} → Γ");
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(
+        (tokens[0].kind.clone(), tokens[0].span.end),
+        (TokenKind::Symbol('Γ'), "Γ\u{0307}".len())
+    );
+    let ev = events("[Γ\u{0307}]");
+    let growth: Vec<_> =
+        ev.iter().filter(|e| e.operator == OperatorClass::DirectionalGrowth).collect();
+    assert_eq!(growth.len(), 1);
+    assert_eq!(growth[0].symbol.as_deref(), Some("Γ̇"));
+}
+
+#[test]
+fn ascii_aliases_are_read_only_on_request() {
+    let prose = "I joined Phi Delta Theta in college";
+    let (results, ev) =
+        MetaEngineImpl::new_default().evaluate_meta("text", prose, &FieldContext::default());
+    assert!(results.iter().all(|r| r.passed), "{:?}", results);
+    assert!(ev.iter().all(|e| e.operator != OperatorClass::Fusion
+        && e.operator != OperatorClass::HarmonicStabilization));
+    let eng = MetaEngineImpl::new_default().with_alias_input(true);
+    let ev = eng.evaluate_meta("text", "Psi -> Omega", &FieldContext::default()).1;
+    let omega =
+        ev.iter().find(|e| e.operator == OperatorClass::ClosureIntegration).expect("Ω gate");
+    assert_eq!(omega.span.map(|s| (s.start, s.end)), Some(("Psi -> ".len(), "Psi -> Omega".len())));
+}
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use ri1_core::constraints::{FieldContext, MetaEngine};
+use ri1_symbolic_meta::catalog::{self, OPERATORS};
+use ri1_symbolic_meta::{GateRegistry, MetaEngineImpl};
+
+#[test]
+fn catalog_entries_are_unique() {
+    let mut glyphs = HashSet::new();
+    let mut keys = HashSet::new();
+    for e in OPERATORS {
+        assert!(keys.insert(e.key), "duplicate key {}", e.key);
+        for g in e.glyphs() {
+            assert!(glyphs.insert(g), "glyph {} listed twice", g);
+            assert_eq!(catalog::entry(g).map(|x| x.symbol), Some(e.symbol));
+        }
+    }
+}
+
+#[test]
+fn engine_and_gates_match_the_catalog() {
+    let eng = MetaEngineImpl::new_default();
+    let defs = eng.operators();
+    assert_eq!(defs.len(), OPERATORS.len());
+    let registry = GateRegistry::builtin();
+    for (e, d) in OPERATORS.iter().zip(defs) {
+        assert_eq!(
+            (d.key.as_str(), d.symbol.as_str(), d.section_ref.as_deref()),
+            (e.key, e.symbol, e.section_ref)
+        );
+        match registry.get(e.symbol) {
+            Some(b) => {
+                assert!(e.gated, "{} has a gate but is not marked gated", e.symbol);
+                assert_eq!(
+                    (b.class, b.section_ref.as_deref()),
+                    (e.class, e.section_ref),
+                    "{}",
+                    e.symbol
+                );
+                assert_eq!(b.glyphs, e.glyphs().collect::<Vec<_>>());
+                for g in e.glyphs() {
+                    assert_eq!(registry.gate_for(g).map(|g| g.symbol()), Some(e.symbol));
+                }
+            }
+            None => assert!(!e.gated, "{} has no gate", e.symbol),
+        }
+    }
+    let gated: Vec<_> = OPERATORS.iter().filter(|e| e.gated).map(|e| e.symbol).collect();
+    assert_eq!(registry.symbols(), gated);
+}
+
+#[test]
+fn events_cite_catalog_sections() {
+    let eng = MetaEngineImpl::new_default();
+    for e in OPERATORS {
+        let content = if e.gated { e.symbol.to_string() } else { "Ψⁿ".to_string() };
+        let (_c, events) = eng.evaluate_meta("text", &content, &FieldContext::default());
+        let cited: Vec<_> =
+            events.iter().filter(|ev| ev.symbol.as_deref() == Some(e.symbol)).collect();
+        assert_eq!(cited.len(), 1, "{}", e.symbol);
+        assert_eq!(
+            (cited[0].operator, cited[0].section_ref.as_deref()),
+            (e.class, e.section_ref),
+            "{}",
+            e.symbol
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use ri1_core::constraints::{
+    ConstraintResult, FieldContext, MetaEngine, OperatorClass, ResonanceEvent, Severity,
+};
+use ri1_symbolic_meta::{MetaEngineImpl, ValidatorConfig};
+
+fn evaluate(cfg: &str, content: &str) -> (Vec<ConstraintResult>, Vec<ResonanceEvent>) {
+    let eng = MetaEngineImpl::new_default()
+        .with_validator_config(ValidatorConfig::from_toml_str(cfg).unwrap());
+    eng.evaluate_meta("text", content, &FieldContext::default())
+}
+
+#[test]
+fn disabled_rules_raise_nothing() {
+    let (_c, events) = evaluate("[rules.PHI006]\nenabled = false\n", "Δ Λ Ψ Ω =");
+    assert!(events.iter().all(|e| e.code.as_deref() != Some("PHI006")));
+    let summary =
+        events.iter().find(|e| e.message.starts_with("interaction_summary")).expect("summary");
+    assert!(summary.message.contains("violations=0"), "{}", summary.message);
+}
+
+#[test]
+fn hard_rules_fail_a_hard_constraint() {
+    let (results, events) = evaluate("[rules.flow-cycle]\nseverity = \"hard\"\n", "Ψ → Δ → Ψ");
+    let cycle = events.iter().find(|e| e.code.as_deref() == Some("PHI004")).expect("cycle event");
+    assert_eq!(cycle.operator, OperatorClass::InteractionViolation);
+    let blocked = results.iter().find(|r| r.name == "PHI004").expect("blocking result");
+    assert!(!blocked.passed && blocked.severity == Severity::Hard);
+
+    let (results, _e) = evaluate("", "Ψ → Δ → Ψ");
+    assert!(results.iter().all(|r| r.passed));
+}
@@ -11,6 +11,7 @@
              severity: Severity::Hard,
              name: self.name(),
              message: if passed { None } else { Some("content must not be empty".into()) },
+             span: None,
          }
      }
  }
@@ -26,6 +27,7 @@
              severity: Severity::Soft,
              name: self.name(),
              message: if passed { None } else { Some(format!("length {} exceeds {}", text.len(), self.0)) },
+             span: None,
          }
      }
  }
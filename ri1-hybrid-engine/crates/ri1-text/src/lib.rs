@@ -17,7 +17,7 @@ mod tests {
     #[test]
     fn basic_text_generates() {
         let m = BasicText;
-        let out = m.generate(GenerationRequest { prompt: "hello".into() });
+        let out = m.generate(GenerationRequest::new("hello"));
         assert!(out.content.contains("hello"));
     }
 }